@@ -2,10 +2,15 @@
 #![feature(let_chains)]
 
 pub mod util {
+    pub mod aho_corasick;
+    pub mod bitset;
+    pub mod counter;
+    pub mod cycle;
     pub mod graph;
     pub mod grid;
     pub mod integer;
     pub mod io;
+    pub mod linalg;
     pub mod parse;
     pub mod trie;
 }
@@ -1,4 +1,7 @@
-use aoc::{util::parse::ParseOps, *};
+use aoc::{
+    util::{io::read, parse::ParseOps},
+    *,
+};
 use clap::Parser;
 use std::{
     io,
@@ -43,23 +46,32 @@ fn main() {
         let filepath = &args.file.as_ref().unwrap_or(&filepath);
 
         let instant = Instant::now();
-        if let Ok((part1, part2)) = wrapper(filepath.to_str().unwrap()) {
-            let elapsed = instant.elapsed();
-
-            solved += if part1.is_some() { 1 } else { 0 };
-            solved += if part2.is_some() { 1 } else { 0 };
-            duration += elapsed;
-
-            println!("{year} Day {day:02}");
-            println!("  Part 1: {}", part1.unwrap_or("unsolved".to_owned()));
-            println!("  Part 2: {}", part2.unwrap_or("unsolved".to_owned()));
-            println!("  Elapsed: {:.03} s", elapsed.as_nanos() as f64 / 1e9);
-            println!();
-        } else {
-            println!("{year} Day {day:02}");
-            println!("  Missing input!");
-            println!("  Place input file in {}", filepath.display());
-            println!();
+        match wrapper(filepath.to_str().unwrap()) {
+            Ok((parsed, part1, part2)) => {
+                let elapsed = instant.elapsed();
+
+                solved += if part1.is_some() { 1 } else { 0 };
+                solved += if part2.is_some() { 1 } else { 0 };
+                duration += elapsed;
+
+                println!("{year} Day {day:02}");
+                println!("  Part 1: {}", part1.unwrap_or("unsolved".to_owned()));
+                println!("  Part 2: {}", part2.unwrap_or("unsolved".to_owned()));
+                println!("  Parse: {:.03} s", parsed.as_nanos() as f64 / 1e9);
+                println!("  Elapsed: {:.03} s", elapsed.as_nanos() as f64 / 1e9);
+                println!();
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                println!("{year} Day {day:02}");
+                println!("  Missing input!");
+                println!("  Place input file in {}", filepath.display());
+                println!();
+            }
+            Err(e) => {
+                println!("{year} Day {day:02}");
+                println!("  Invalid input: {e}");
+                println!();
+            }
         }
     }
 
@@ -74,11 +86,13 @@ struct Solution {
     year: u32,
     day: u32,
     filepath: PathBuf,
-    wrapper: fn(&str) -> io::Result<(Option<String>, Option<String>)>,
+    wrapper: fn(&str) -> io::Result<(Duration, Option<String>, Option<String>)>,
 }
 
+/// Days followed by `+ validate` have their input checked by the day's `validate` hook before it
+/// is parsed.
 macro_rules! make_solutions {
-    ($year:tt $($day:tt),*) => {
+    ($year:tt $($day:tt $(+ $validate:ident)?),*) => {
         fn $year() -> Vec<Solution> {
             vec![$({
                 let year = stringify!($year);
@@ -89,11 +103,14 @@ macro_rules! make_solutions {
                 let wrapper = |filepath: &str| {
                     use $year::$day::*;
 
+                    let instant = Instant::now();
+                    $($validate(&read(filepath)?)?;)?
                     let input = parse(filepath)?;
+                    let parsed = instant.elapsed();
                     let part1 = part1(&input);
                     let part2 = part2(&input);
 
-                    Ok((part1.map(|v| v.to_string()), part2.map(|v| v.to_string())))
+                    Ok((parsed, part1.map(|v| v.to_string()), part2.map(|v| v.to_string())))
                 };
 
                 Solution { year: year.unsigned(), day: day.unsigned(), filepath, wrapper }
@@ -103,8 +120,8 @@ macro_rules! make_solutions {
 }
 
 make_solutions!(y2024
-    day01, day02, day03, day04, day05,
-    day06, day07, day08, day09, day10,
-    day11, day12, day13, day14, day15,
-    day16, day17, day18, day19, day20
+    day01, day02, day03, day04 + validate, day05,
+    day06 + validate, day07, day08 + validate, day09, day10 + validate,
+    day11, day12 + validate, day13, day14, day15 + validate,
+    day16 + validate, day17, day18, day19, day20 + validate
 );
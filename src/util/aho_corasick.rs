@@ -0,0 +1,135 @@
+use crate::util::trie::{Symbol, Trie};
+use std::iter::FusedIterator;
+
+/// Occurrence of pattern number `pattern` at `text[start..end]`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+struct State<K> {
+    next: Vec<(K, usize)>,
+    fail: usize,
+    /// Patterns ending here, including those reached through failure links.
+    outputs: Vec<usize>,
+}
+
+impl<K: Symbol> State<K> {
+    fn goto(&self, k: K) -> Option<usize> {
+        self.next.binary_search_by(|(c, _)| c.cmp(&k)).ok().map(|i| self.next[i].1)
+    }
+}
+
+/// Automaton finding every occurrence of a set of patterns in a single pass over the text, with
+/// the goto function taken from a [`Trie`] of the patterns.
+pub struct AhoCorasick<K = u8> {
+    states: Vec<State<K>>,
+    lens: Vec<usize>,
+}
+
+impl<K: Symbol> AhoCorasick<K> {
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[K]>,
+    {
+        let patterns = patterns.into_iter().collect::<Vec<_>>();
+
+        let mut trie = Trie::new();
+        for p in &patterns {
+            trie.insert_symbols(p.as_ref().iter().copied());
+        }
+
+        let edges = trie.edges_bfs();
+        let mut states = (0..=edges.len())
+            .map(|_| State { next: Vec::new(), fail: 0, outputs: Vec::new() })
+            .collect::<Vec<_>>();
+        for (child, &(parent, k)) in edges.iter().enumerate() {
+            states[parent].next.push((k, child + 1));
+        }
+
+        let mut lens = Vec::with_capacity(patterns.len());
+        for (id, p) in patterns.iter().enumerate() {
+            let end = p.as_ref().iter().fold(0, |s, &k| states[s].goto(k).unwrap());
+            states[end].outputs.push(id);
+            lens.push(p.as_ref().len());
+        }
+
+        // States are numbered breadth-first, so failure links always point at finished states
+        for (child, &(parent, k)) in edges.iter().enumerate() {
+            let child = child + 1;
+            if parent != 0 {
+                let mut f = states[parent].fail;
+                states[child].fail = loop {
+                    if let Some(next) = states[f].goto(k) {
+                        break next;
+                    }
+                    if f == 0 {
+                        break 0;
+                    }
+                    f = states[f].fail;
+                };
+            }
+
+            let inherited = states[states[child].fail].outputs.clone();
+            states[child].outputs.extend(inherited);
+        }
+
+        Self { states, lens }
+    }
+
+    pub fn pattern_count(&self) -> usize { self.lens.len() }
+
+    pub fn pattern_len(&self, pattern: usize) -> usize { self.lens[pattern] }
+
+    /// All, possibly overlapping, matches in `text`, ordered by their end position.
+    pub fn find_iter<'a>(&'a self, text: &'a [K]) -> FindIter<'a, K> {
+        FindIter { ac: self, text, pos: 0, state: 0, output: 0 }
+    }
+
+    pub fn count(&self, text: &[K]) -> usize { self.find_iter(text).count() }
+
+    fn step(&self, mut state: usize, k: K) -> usize {
+        loop {
+            if let Some(next) = self.states[state].goto(k) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.states[state].fail;
+        }
+    }
+}
+
+/// Iterator returned by [`AhoCorasick::find_iter`].
+pub struct FindIter<'a, K> {
+    ac: &'a AhoCorasick<K>,
+    text: &'a [K],
+    pos: usize,
+    state: usize,
+    output: usize,
+}
+
+impl<K: Symbol> Iterator for FindIter<'_, K> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            if let Some(&pattern) = self.ac.states[self.state].outputs.get(self.output) {
+                self.output += 1;
+                let len = self.ac.lens[pattern];
+                return Some(Match { pattern, start: self.pos - len, end: self.pos });
+            }
+
+            let &k = self.text.get(self.pos)?;
+            self.state = self.ac.step(self.state, k);
+            self.pos += 1;
+            self.output = 0;
+        }
+    }
+}
+
+impl<K: Symbol> FusedIterator for FindIter<'_, K> {}
@@ -0,0 +1,123 @@
+/// Fixed-size set of the integers `0..capacity`, one bit each.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    pub fn new(capacity: usize) -> Self { Self { words: vec![0; capacity.div_ceil(64)], capacity } }
+
+    pub fn capacity(&self) -> usize { self.capacity }
+
+    /// Number of members.
+    pub fn len(&self) -> usize { self.words.iter().map(|w| w.count_ones() as usize).sum() }
+
+    pub fn is_empty(&self) -> bool { self.words.iter().all(|&w| w == 0) }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.capacity && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Returns whether `i` was newly added.
+    pub fn insert(&mut self, i: usize) -> bool {
+        assert!(i < self.capacity, "index {i} out of bounds for capacity {}", self.capacity);
+        let word = &mut self.words[i / 64];
+        let added = *word & (1 << (i % 64)) == 0;
+        *word |= 1 << (i % 64);
+        added
+    }
+
+    /// Returns whether `i` was a member.
+    pub fn remove(&mut self, i: usize) -> bool {
+        if i >= self.capacity {
+            return false;
+        }
+        let word = &mut self.words[i / 64];
+        let removed = *word & (1 << (i % 64)) != 0;
+        *word &= !(1 << (i % 64));
+        removed
+    }
+
+    pub fn clear(&mut self) { self.words.fill(0) }
+
+    pub fn union_with(&mut self, other: &BitSet) {
+        assert_eq!(self.capacity, other.capacity, "bitsets differ in capacity");
+        self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a |= b);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        assert_eq!(self.capacity, other.capacity, "bitsets differ in capacity");
+        self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= b);
+    }
+
+    pub fn difference_with(&mut self, other: &BitSet) {
+        assert_eq!(self.capacity, other.capacity, "bitsets differ in capacity");
+        self.words.iter_mut().zip(&other.words).for_each(|(a, b)| *a &= !b);
+    }
+
+    /// Members in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// Set of positions on a `w` by `h` grid, backed by a single row-major [`BitSet`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitGrid {
+    w: usize,
+    h: usize,
+    bits: BitSet,
+}
+
+impl BitGrid {
+    pub fn new(w: usize, h: usize) -> Self { Self { w, h, bits: BitSet::new(w * h) } }
+
+    pub fn w(&self) -> usize { self.w }
+
+    pub fn h(&self) -> usize { self.h }
+
+    /// Number of set positions.
+    pub fn len(&self) -> usize { self.bits.len() }
+
+    pub fn is_empty(&self) -> bool { self.bits.is_empty() }
+
+    /// Out of bounds positions are never set.
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.h && pos.1 < self.w && self.bits.contains(pos.0 * self.w + pos.1)
+    }
+
+    /// Returns whether `pos` was newly set.
+    pub fn insert(&mut self, pos: (usize, usize)) -> bool {
+        let in_bounds = pos.0 < self.h && pos.1 < self.w;
+        assert!(in_bounds, "{pos:?} out of bounds for {}x{}", self.w, self.h);
+        self.bits.insert(pos.0 * self.w + pos.1)
+    }
+
+    /// Returns whether `pos` was set.
+    pub fn remove(&mut self, pos: (usize, usize)) -> bool {
+        pos.0 < self.h && pos.1 < self.w && self.bits.remove(pos.0 * self.w + pos.1)
+    }
+
+    pub fn clear(&mut self) { self.bits.clear() }
+
+    pub fn union_with(&mut self, other: &BitGrid) { self.bits.union_with(&other.bits) }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) { self.bits.intersect_with(&other.bits) }
+
+    pub fn difference_with(&mut self, other: &BitGrid) { self.bits.difference_with(&other.bits) }
+
+    /// Set positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bits.iter().map(|i| (i / self.w, i % self.w))
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map, HashMap},
+    hash::Hash,
+    mem,
+};
+
+/// Multiset storing how many times each key occurs.
+#[derive(Clone, Debug)]
+pub struct Counter<K> {
+    counts: HashMap<K, usize>,
+    /// Emptied buffer reused by [`Counter::step`] to avoid reallocating every generation.
+    spare: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self { Self { counts: HashMap::new(), spare: HashMap::new() } }
+
+    /// Adds `n` occurrences of `key`.
+    pub fn add(&mut self, key: K, n: usize) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    pub fn get(&self, key: &K) -> usize { self.counts.get(key).copied().unwrap_or(0) }
+
+    /// Number of distinct keys.
+    pub fn len(&self) -> usize { self.counts.len() }
+
+    pub fn is_empty(&self) -> bool { self.counts.is_empty() }
+
+    /// Number of occurrences over all keys.
+    pub fn total(&self) -> usize { self.counts.values().sum() }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> + '_ {
+        self.counts.iter().map(|(k, n)| (k, *n))
+    }
+
+    /// Adds all occurrences from `other`.
+    pub fn merge(&mut self, other: Counter<K>) {
+        for (k, n) in other.counts {
+            self.add(k, n);
+        }
+    }
+
+    /// The `n` keys occurring most often, most common first. Ties are in no particular order.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, usize)> {
+        let mut counts = self.iter().collect::<Vec<_>>();
+        counts.sort_unstable_by_key(|&(_, n)| Reverse(n));
+        counts.truncate(n);
+        counts
+    }
+
+    /// Advances a population by one generation: every key is replaced by the keys `f` maps it to,
+    /// each of which inherits all of its occurrences.
+    pub fn step<F, I>(&mut self, mut f: F)
+    where
+        F: FnMut(&K) -> I,
+        I: IntoIterator<Item = K>,
+    {
+        let mut next = mem::take(&mut self.spare);
+        for (k, n) in self.counts.drain() {
+            for next_k in f(&k) {
+                *next.entry(next_k).or_insert(0) += n;
+            }
+        }
+        self.spare = mem::replace(&mut self.counts, next);
+    }
+}
+
+impl<K: Hash + Eq> Default for Counter<K> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<K: Hash + Eq> PartialEq for Counter<K> {
+    fn eq(&self, other: &Self) -> bool { self.counts == other.counts }
+}
+
+impl<K: Hash + Eq> Eq for Counter<K> {}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for k in iter {
+            self.add(k, 1);
+        }
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K> IntoIterator for Counter<K> {
+    type IntoIter = hash_map::IntoIter<K, usize>;
+    type Item = (K, usize);
+
+    fn into_iter(self) -> Self::IntoIter { self.counts.into_iter() }
+}
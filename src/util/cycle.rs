@@ -0,0 +1,131 @@
+//! Cycle detection in sequences `x0, f(x0), f(f(x0)), ...`. Transition functions return `None`
+//! for states without a successor, in which case the sequence ends and has no cycle.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The sequence first repeats at index `start + period`, which equals the state at `start`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CycleSpan {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl CycleSpan {
+    /// Earliest index holding the same state as index `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Floyd's tortoise and hare, using constant memory.
+pub fn floyd<T, F>(x0: T, mut f: F) -> Option<CycleSpan>
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> Option<T>,
+{
+    let mut tortoise = f(&x0)?;
+    let mut hare = f(&tortoise)?;
+    while tortoise != hare {
+        tortoise = f(&tortoise)?;
+        let next = f(&hare)?;
+        hare = f(&next)?;
+    }
+
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise)?;
+        hare = f(&hare)?;
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise)?;
+    while tortoise != hare {
+        hare = f(&hare)?;
+        period += 1;
+    }
+
+    Some(CycleSpan { start, period })
+}
+
+/// Brent's algorithm, using constant memory and fewer evaluations of `f` than [`floyd`].
+pub fn brent<T, F>(x0: T, mut f: F) -> Option<CycleSpan>
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> Option<T>,
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0)?;
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare)?;
+        period += 1;
+    }
+
+    let mut start = 0;
+    tortoise = x0.clone();
+    hare = x0;
+    for _ in 0..period {
+        hare = f(&hare)?;
+    }
+    while tortoise != hare {
+        tortoise = f(&tortoise)?;
+        hare = f(&hare)?;
+        start += 1;
+    }
+
+    Some(CycleSpan { start, period })
+}
+
+/// Remembers every state, evaluating `f` only once per state before the first repeat.
+pub fn hashed<T, F>(x0: T, mut f: F) -> Option<CycleSpan>
+where
+    T: Hash + Eq,
+    F: FnMut(&T) -> Option<T>,
+{
+    let mut seen = HashMap::new();
+    let mut x = x0;
+    for i in 0.. {
+        if let Some(&start) = seen.get(&x) {
+            return Some(CycleSpan { start, period: i - start });
+        }
+        let next = f(&x)?;
+        seen.insert(x, i);
+        x = next;
+    }
+    unreachable!()
+}
+
+/// The state at index `n`, skipping ahead once the sequence starts repeating. `None` if the
+/// sequence ends before that.
+pub fn state_after<T, F>(x0: T, mut f: F, n: usize) -> Option<T>
+where
+    T: Clone + Hash + Eq,
+    F: FnMut(&T) -> Option<T>,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut x = x0;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&x) {
+            let cycle = CycleSpan { start, period: i - start };
+            return Some(states.swap_remove(cycle.reduce(n)));
+        }
+        let next = f(&x)?;
+        seen.insert(x.clone(), i);
+        states.push(x);
+        x = next;
+    }
+    Some(x)
+}
@@ -1,18 +1,25 @@
+use crate::util::integer::Unsigned;
+use priority_queue::PriorityQueue;
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Reverse,
+    collections::{
+        hash_map::{Entry, Keys},
+        HashMap, HashSet,
+    },
+    fmt::{Debug, Display},
     hash::Hash,
 };
 
-pub struct Graph<T, V>
+pub struct Graph<T, V, E = ()>
 where
     T: Eq + Hash,
     V: Eq + Hash,
 {
     nodes: HashMap<T, V>,
-    edges: HashMap<T, HashSet<T>>,
+    edges: HashMap<T, HashMap<T, E>>,
 }
 
-impl<T, V> Graph<T, V>
+impl<T, V, E> Graph<T, V, E>
 where
     T: Eq + Hash,
     V: Eq + Hash,
@@ -21,18 +28,859 @@ where
 
     pub fn add_node(&mut self, id: T, value: V) { self.nodes.insert(id, value); }
 
-    pub fn add_edge(&mut self, from: T, to: T) {
-        match self.edges.get_mut(&from) {
-            None => {
-                self.edges.insert(from, HashSet::from([to]));
+    pub fn add_edge(&mut self, from: T, to: T)
+    where
+        E: Default,
+    {
+        self.add_weighted_edge(from, to, E::default());
+    }
+
+    pub fn add_weighted_edge(&mut self, from: T, to: T, weight: E) {
+        self.edges.entry(from).or_default().insert(to, weight);
+    }
+
+    /// Adds edges in both directions.
+    pub fn add_undirected_edge(&mut self, a: T, b: T)
+    where
+        T: Clone,
+        E: Default,
+    {
+        self.add_edge(a.clone(), b.clone());
+        self.add_edge(b, a);
+    }
+
+    pub fn val(&self, id: &T) -> Option<&V> { self.nodes.get(id) }
+
+    pub fn adj(&self, from: &T) -> Option<Keys<'_, T, E>> {
+        self.edges.get(from).map(|adj| adj.keys())
+    }
+
+    pub fn edges(&self, from: &T) -> Option<&HashMap<T, E>> { self.edges.get(from) }
+
+    pub fn weight(&self, from: &T, to: &T) -> Option<&E> {
+        self.edges.get(from).and_then(|adj| adj.get(to))
+    }
+
+    pub fn has_edge(&self, from: &T, to: &T) -> bool { self.weight(from, to).is_some() }
+}
+
+impl<T, V, E> Graph<T, V, E>
+where
+    T: Clone + Eq + Hash,
+    V: Eq + Hash,
+{
+    /// Builds the part of an implicit graph reachable from `start`, where `successors` yields
+    /// each neighbor of a node with the weight of the edge leading to it.
+    pub fn explore<FV, FN, IN>(start: T, mut value: FV, mut successors: FN) -> Self
+    where
+        FV: FnMut(&T) -> V,
+        FN: FnMut(&T) -> IN,
+        IN: IntoIterator<Item = (T, E)>,
+    {
+        let mut graph = Self::new();
+        let mut stack = vec![start];
+        while let Some(n) = stack.pop() {
+            if graph.nodes.contains_key(&n) {
+                continue;
             }
-            Some(adj) => {
-                adj.insert(to);
+            graph.add_node(n.clone(), value(&n));
+            for (m, w) in successors(&n) {
+                if !graph.nodes.contains_key(&m) {
+                    stack.push(m.clone());
+                }
+                graph.add_weighted_edge(n.clone(), m, w);
             }
         }
+        graph
     }
+}
 
-    pub fn val(&self, id: &T) -> Option<&V> { self.nodes.get(id) }
+impl<T, V, E> Graph<T, V, E>
+where
+    T: Clone + Debug + Eq + Hash,
+    V: Eq + Hash,
+{
+    /// GraphViz DOT rendering, labeling each node with its id. See [`Graph::to_dot_with_values`]
+    /// to label nodes with their values instead.
+    pub fn to_dot(&self) -> String { self.to_dot_with(|id, _| format!("{id:?}"), |_| None) }
+
+    /// GraphViz DOT rendering, labeling each node with its value, or its id if it has none.
+    pub fn to_dot_with_values(&self) -> String
+    where
+        V: Display,
+    {
+        self.to_dot_with(|id, v| v.map_or_else(|| format!("{id:?}"), V::to_string), |_| None)
+    }
+
+    /// GraphViz DOT rendering, labeling each node with its id and each edge with its weight.
+    pub fn to_weighted_dot(&self) -> String
+    where
+        E: Display,
+    {
+        self.to_dot_with(|id, _| format!("{id:?}"), |w| Some(w.to_string()))
+    }
+
+    /// GraphViz DOT rendering with custom labels. Node ids are written using their `Debug`
+    /// representation, and statements are sorted so that output is stable between runs.
+    pub fn to_dot_with<FN, FE>(&self, mut node_label: FN, mut edge_label: FE) -> String
+    where
+        FN: FnMut(&T, Option<&V>) -> String,
+        FE: FnMut(&E) -> Option<String>,
+    {
+        let mut nodes = self
+            .ids()
+            .into_iter()
+            .map(|id| {
+                let label = node_label(&id, self.val(&id));
+                format!("    {} [label={}];", dot_quote(&id), dot_escape(&label))
+            })
+            .collect::<Vec<_>>();
+        nodes.sort();
+
+        let mut edges = Vec::new();
+        for (from, adj) in &self.edges {
+            for (to, w) in adj {
+                let attrs = edge_label(w)
+                    .map(|label| format!(" [label={}]", dot_escape(&label)))
+                    .unwrap_or_default();
+                edges.push(format!("    {} -> {}{attrs};", dot_quote(from), dot_quote(to)));
+            }
+        }
+        edges.sort();
+
+        let mut dot = String::from("digraph {\n");
+        for line in nodes.into_iter().chain(edges) {
+            dot.push_str(&line);
+            dot.push('\n');
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// One `from -> to` line per edge, sorted.
+    pub fn to_edge_list(&self) -> String { self.edge_list_with(|_| None) }
+
+    /// One `from -> to: weight` line per edge, sorted.
+    pub fn to_weighted_edge_list(&self) -> String
+    where
+        E: Display,
+    {
+        self.edge_list_with(|w| Some(w.to_string()))
+    }
+
+    fn edge_list_with<FE>(&self, mut edge_label: FE) -> String
+    where
+        FE: FnMut(&E) -> Option<String>,
+    {
+        let mut lines = Vec::new();
+        for (from, adj) in &self.edges {
+            for (to, w) in adj {
+                lines.push(match edge_label(w) {
+                    Some(label) => format!("{from:?} -> {to:?}: {label}\n"),
+                    None => format!("{from:?} -> {to:?}\n"),
+                });
+            }
+        }
+        lines.sort();
+        lines.concat()
+    }
+}
+
+fn dot_quote<T: Debug>(id: &T) -> String { dot_escape(&format!("{id:?}")) }
+
+fn dot_escape(s: &str) -> String { format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")) }
+
+impl<T, V, E> Graph<T, V, E>
+where
+    T: Clone + Eq + Hash,
+    V: Eq + Hash,
+{
+    /// Components of the graph with every edge treated as undirected.
+    pub fn connected_components(&self) -> Vec<Vec<T>> {
+        let ids = self.ids().into_iter().collect::<Vec<_>>();
+        let index = ids.iter().enumerate().map(|(i, n)| (n, i)).collect::<HashMap<_, _>>();
+
+        let mut sets = UnionFind::new(ids.len());
+        for (from, adj) in &self.edges {
+            for to in adj.keys() {
+                sets.union(index[from], index[to]);
+            }
+        }
+
+        let mut components = HashMap::<usize, Vec<T>>::new();
+        for (i, n) in ids.iter().enumerate() {
+            components.entry(sets.find(i)).or_default().push(n.clone());
+        }
+        components.into_values().collect()
+    }
+
+    /// Every maximal clique of the graph with every edge treated as undirected
+    /// (Bron–Kerbosch with pivoting).
+    pub fn maximal_cliques(&self) -> Vec<Vec<T>> {
+        let neighbors = self.undirected_neighbors();
+        let mut cliques = Vec::new();
+        if neighbors.is_empty() {
+            return cliques;
+        }
+        bron_kerbosch(
+            &neighbors,
+            &mut Vec::new(),
+            neighbors.keys().cloned().collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+        cliques
+    }
+
+    pub fn maximum_clique(&self) -> Vec<T> {
+        self.maximal_cliques().into_iter().max_by_key(Vec::len).unwrap_or_default()
+    }
+
+    /// Every clique of exactly `k` nodes that includes `node`.
+    pub fn cliques_containing(&self, node: &T, k: usize) -> Vec<Vec<T>> {
+        fn extend<T: Clone + Eq + Hash>(
+            neighbors: &HashMap<T, HashSet<T>>,
+            clique: &mut Vec<T>,
+            candidates: &[T],
+            k: usize,
+            cliques: &mut Vec<Vec<T>>,
+        ) {
+            if clique.len() == k {
+                cliques.push(clique.clone());
+                return;
+            }
+            for (i, c) in candidates.iter().enumerate() {
+                if clique.iter().all(|n| neighbors[n].contains(c)) {
+                    clique.push(c.clone());
+                    extend(neighbors, clique, &candidates[i + 1..], k, cliques);
+                    clique.pop();
+                }
+            }
+        }
+
+        let neighbors = self.undirected_neighbors();
+        let Some(adj) = neighbors.get(node) else {
+            return if k == 1 { vec![vec![node.clone()]] } else { Vec::new() };
+        };
+
+        let mut cliques = Vec::new();
+        if k > 0 {
+            let candidates = adj.iter().cloned().collect::<Vec<_>>();
+            extend(&neighbors, &mut vec![node.clone()], &candidates, k, &mut cliques);
+        }
+        cliques
+    }
+
+    fn undirected_neighbors(&self) -> HashMap<T, HashSet<T>> {
+        let mut neighbors =
+            self.ids().into_iter().map(|n| (n, HashSet::new())).collect::<HashMap<_, _>>();
+        for (from, adj) in &self.edges {
+            for to in adj.keys().filter(|to| *to != from) {
+                neighbors.get_mut(from).unwrap().insert(to.clone());
+                neighbors.get_mut(to).unwrap().insert(from.clone());
+            }
+        }
+        neighbors
+    }
+}
+
+fn bron_kerbosch<T>(
+    neighbors: &HashMap<T, HashSet<T>>,
+    r: &mut Vec<T>,
+    mut p: HashSet<T>,
+    mut x: HashSet<T>,
+    cliques: &mut Vec<Vec<T>>,
+) where
+    T: Clone + Eq + Hash,
+{
+    if p.is_empty() {
+        if x.is_empty() {
+            cliques.push(r.clone());
+        }
+        return;
+    }
+
+    let pivot = p.union(&x).max_by_key(|n| neighbors[*n].intersection(&p).count()).unwrap();
+    let candidates = p.difference(&neighbors[pivot]).cloned().collect::<Vec<_>>();
+
+    for v in candidates {
+        let adj = &neighbors[&v];
+        r.push(v.clone());
+        bron_kerbosch(
+            neighbors,
+            r,
+            p.intersection(adj).cloned().collect(),
+            x.intersection(adj).cloned().collect(),
+            cliques,
+        );
+        r.pop();
+        p.remove(&v);
+        x.insert(v);
+    }
+}
+
+/// Disjoint-set forest over `0..n` with path compression and union by size.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self { Self { parent: (0..n).collect(), size: vec![1; n], count: n } }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut curr = x;
+        while self.parent[curr] != root {
+            curr = std::mem::replace(&mut self.parent[curr], root);
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning false if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool { self.find(a) == self.find(b) }
+
+    /// Size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize { self.count }
+}
+
+/// A closed walk through the graph, listed from its first node back around to the node
+/// preceding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T, V, E> Graph<T, V, E>
+where
+    T: Clone + Eq + Hash,
+    V: Eq + Hash,
+{
+    /// Every node id known to the graph, whether added with a value or only seen on an edge.
+    pub fn ids(&self) -> HashSet<T> {
+        self.nodes
+            .keys()
+            .chain(self.edges.keys())
+            .chain(self.edges.values().flat_map(|adj| adj.keys()))
+            .cloned()
+            .collect()
+    }
+
+    /// Orders every node so that each edge points forwards, or returns a cycle preventing it.
+    pub fn topological_sort(&self) -> Result<Vec<T>, Cycle<T>> {
+        self.topological_sort_subset(&self.ids().into_iter().collect::<Vec<_>>())
+    }
+
+    /// Topological order of the subgraph induced by `subset`, ignoring edges leaving it. Ties
+    /// are broken by the order of `subset`, duplicates only count once.
+    pub fn topological_sort_subset(&self, subset: &[T]) -> Result<Vec<T>, Cycle<T>> {
+        let mut members = HashSet::new();
+        let subset = subset.iter().filter(|n| members.insert(*n)).collect::<Vec<_>>();
+
+        let mut in_degree = subset.iter().map(|&n| (n, 0)).collect::<HashMap<_, _>>();
+        for &n in &subset {
+            for m in self.adj(n).into_iter().flatten().filter(|m| members.contains(m)) {
+                *in_degree.get_mut(m).unwrap() += 1;
+            }
+        }
+
+        let mut stack =
+            subset.iter().rev().filter(|n| in_degree[**n] == 0).copied().collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(subset.len());
+        while let Some(n) = stack.pop() {
+            order.push(n.clone());
+            for m in self.adj(n).into_iter().flatten().filter(|m| members.contains(m)) {
+                let d = in_degree.get_mut(m).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    stack.push(m);
+                }
+            }
+        }
+
+        if order.len() == members.len() {
+            return Ok(order);
+        }
+        let remaining = in_degree.into_iter().filter(|(_, d)| *d > 0).map(|(n, _)| n.clone());
+        let cycle = self.find_cycle_in(remaining.collect());
+        Err(cycle.expect("nodes left with incoming edges always contain a cycle"))
+    }
+
+    pub fn is_acyclic(&self) -> bool { self.find_cycle().is_none() }
+
+    pub fn find_cycle(&self) -> Option<Cycle<T>> { self.find_cycle_in(self.ids()) }
+
+    fn find_cycle_in(&self, members: HashSet<T>) -> Option<Cycle<T>> {
+        let mut done = HashSet::<T>::new();
+
+        for root in &members {
+            if done.contains(root) {
+                continue;
+            }
+
+            let mut path = vec![root.clone()];
+            let mut on_path = HashSet::from([root.clone()]);
+            let mut work = vec![self.adj(root).into_iter().flatten().collect::<Vec<_>>()];
+
+            while let Some(succs) = work.last_mut() {
+                let Some(next) = succs.pop() else {
+                    let n = path.pop().unwrap();
+                    on_path.remove(&n);
+                    done.insert(n);
+                    work.pop();
+                    continue;
+                };
+
+                if !members.contains(next) || done.contains(next) {
+                    continue;
+                }
+                if on_path.contains(next) {
+                    let start = path.iter().position(|n| n == next).unwrap();
+                    return Some(Cycle(path.split_off(start)));
+                }
+
+                path.push(next.clone());
+                on_path.insert(next.clone());
+                work.push(self.adj(next).into_iter().flatten().collect());
+            }
+        }
+
+        None
+    }
+
+    /// For every node of a DAG, the number of distinct paths starting at it and ending at a node
+    /// accepted by `is_target`, or `None` if it overflows. Targets may lie in the middle of longer
+    /// paths, in which case both the path ending there and those continuing past it are counted.
+    pub fn path_counts<F>(&self, mut is_target: F) -> Result<HashMap<T, Option<u128>>, Cycle<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut counts = HashMap::new();
+        for n in self.topological_sort()?.into_iter().rev() {
+            let mut succs = self.adj(&n).into_iter().flatten();
+            let count = succs.try_fold(is_target(&n) as u128, |acc, m| acc.checked_add(counts[m]?));
+            counts.insert(n, count);
+        }
+        Ok(counts)
+    }
+
+    /// Total number of paths from any of `sources` to any node accepted by `is_target`, or `None`
+    /// if it overflows.
+    pub fn count_paths<'a, I, F>(&self, sources: I, is_target: F) -> Result<Option<u128>, Cycle<T>>
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a T>,
+        F: FnMut(&T) -> bool,
+    {
+        let counts = self.path_counts(is_target)?;
+        Ok(sources
+            .into_iter()
+            .try_fold(0u128, |acc, n| acc.checked_add(counts.get(n).copied().unwrap_or(Some(0))?)))
+    }
+
+    /// For every node of a DAG, the set of nodes accepted by `is_target` reachable from it.
+    pub fn reachable_targets<F>(&self, mut is_target: F) -> Result<HashMap<T, HashSet<T>>, Cycle<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut reachable = HashMap::<T, HashSet<T>>::new();
+        for n in self.topological_sort()?.into_iter().rev() {
+            let mut targets = HashSet::new();
+            if is_target(&n) {
+                targets.insert(n.clone());
+            }
+            for m in self.adj(&n).into_iter().flatten() {
+                targets.extend(reachable[m].iter().cloned());
+            }
+            reachable.insert(n, targets);
+        }
+        Ok(reachable)
+    }
+
+    /// Sum over `sources` of the number of distinct targets reachable from each.
+    pub fn count_reachable<'a, I, F>(&self, sources: I, is_target: F) -> Result<usize, Cycle<T>>
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a T>,
+        F: FnMut(&T) -> bool,
+    {
+        let reachable = self.reachable_targets(is_target)?;
+        Ok(sources.into_iter().map(|n| reachable.get(n).map_or(0, HashSet::len)).sum())
+    }
+
+    /// Strongly connected components in reverse topological order (Tarjan's algorithm).
+    pub fn strongly_connected_components(&self) -> Vec<Vec<T>> {
+        let mut next_index = 0;
+        let mut index = HashMap::<T, usize>::new();
+        let mut low_link = HashMap::<T, usize>::new();
+        let mut stack = Vec::<T>::new();
+        let mut on_stack = HashSet::<T>::new();
+        let mut components = Vec::new();
+
+        for root in self.ids() {
+            if index.contains_key(&root) {
+                continue;
+            }
 
-    pub fn adj(&self, from: &T) -> Option<&HashSet<T>> { self.edges.get(from) }
+            let mut work = vec![(root, 0)];
+            while let Some((v, i)) = work.last_mut() {
+                if *i == 0 {
+                    index.insert(v.clone(), next_index);
+                    low_link.insert(v.clone(), next_index);
+                    next_index += 1;
+                    stack.push(v.clone());
+                    on_stack.insert(v.clone());
+                }
+
+                let v = v.clone();
+                if let Some(w) = self.adj(&v).into_iter().flatten().nth(*i) {
+                    *i += 1;
+                    if !index.contains_key(w) {
+                        work.push((w.clone(), 0));
+                    } else if on_stack.contains(w) {
+                        let low = low_link[&v].min(index[w]);
+                        low_link.insert(v, low);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some((u, _)) = work.last() {
+                    let low = low_link[u].min(low_link[&v]);
+                    low_link.insert(u.clone(), low);
+                }
+
+                if low_link[&v] == index[&v] {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack.remove(&w);
+                        component.push(w.clone());
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+}
+
+impl<T, V, E> Graph<T, V, E>
+where
+    T: Clone + Eq + Hash,
+    V: Eq + Hash,
+    E: Unsigned<E> + Ord,
+{
+    /// Cheapest path from `start` to `goal`, returned as its total weight and the nodes along it.
+    pub fn dijkstra(&self, start: &T, goal: &T) -> Option<(E, Vec<T>)> {
+        self.a_star(start, goal, |_| E::ZERO)
+    }
+
+    /// Same as [`Graph::dijkstra`], guided by `heuristic`, which must never overestimate the
+    /// remaining cost to `goal`.
+    pub fn a_star<H>(&self, start: &T, goal: &T, heuristic: H) -> Option<(E, Vec<T>)>
+    where
+        H: FnMut(&T) -> E,
+    {
+        a_star(
+            start.clone(),
+            |n| self.edges(n).into_iter().flatten().map(|(to, w)| (to.clone(), *w)),
+            heuristic,
+            |n| n == goal,
+        )
+    }
+}
+
+/// Dijkstra's algorithm over an implicit graph, where `successors` yields each neighbor of a
+/// node along with the cost of moving to it. Stops at the first node accepted by `success`.
+pub fn dijkstra<N, C, FN, IN, FS>(start: N, successors: FN, success: FS) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Unsigned<C> + Ord,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    a_star(start, successors, |_| C::ZERO, success)
+}
+
+/// A* over an implicit graph. `heuristic` must be admissible for the returned path to be
+/// optimal.
+pub fn a_star<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Unsigned<C> + Ord,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut open_set = PriorityQueue::<N, Reverse<C>>::new();
+    let mut came_from = HashMap::<N, N>::new();
+    let mut g_score = HashMap::<N, C>::new();
+
+    g_score.insert(start.clone(), C::ZERO);
+    open_set.push(start.clone(), Reverse(heuristic(&start)));
+
+    while let Some((curr, _)) = open_set.pop() {
+        let g = g_score[&curr];
+        if success(&curr) {
+            return Some((g, reconstruct_path(&came_from, curr)));
+        }
+
+        for (adj, d) in successors(&curr) {
+            let tentative_g_score = g + d;
+            match g_score.entry(adj.clone()) {
+                Entry::Occupied(e) if *e.get() <= tentative_g_score => continue,
+                Entry::Occupied(mut e) => {
+                    e.insert(tentative_g_score);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(tentative_g_score);
+                }
+            }
+            came_from.insert(adj.clone(), curr.clone());
+            let f_score = tentative_g_score + heuristic(&adj);
+            open_set.push_increase(adj, Reverse(f_score));
+        }
+    }
+
+    None
+}
+
+/// Every optimal route from a single start, as found by [`dijkstra_all`]. Instead of a single
+/// path this keeps, for each node, all predecessors that reach it at minimal cost, forming a DAG
+/// rooted at `start` whose sinks are `goals`.
+pub struct ShortestPaths<N, C>
+where
+    N: Eq + Hash,
+{
+    pub start: N,
+    pub goals: Vec<N>,
+    pub cost: C,
+    dist: HashMap<N, C>,
+    preds: HashMap<N, Vec<N>>,
+}
+
+impl<N, C> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord,
+{
+    /// Minimal cost from `start` to `node`, for every node settled before the search finished.
+    pub fn cost_to(&self, node: &N) -> Option<C> { self.dist.get(node).copied() }
+
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.preds.get(node).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Nodes lying on at least one optimal path from `start` to any of the `goals`.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut visited = HashSet::new();
+        let mut stack = self.goals.clone();
+        while let Some(n) = stack.pop() {
+            if visited.insert(n.clone()) {
+                stack.extend(self.predecessors(&n).iter().cloned());
+            }
+        }
+        visited
+    }
+
+    /// Edges `(from, to)` lying on at least one optimal path.
+    pub fn edges(&self) -> HashSet<(N, N)> {
+        self.nodes()
+            .into_iter()
+            .flat_map(|n| self.predecessors(&n).iter().map(move |p| (p.clone(), n.clone())))
+            .collect()
+    }
+
+    /// Number of distinct optimal paths from `start` to any of the `goals`, or `None` if it
+    /// overflows.
+    pub fn count_paths(&self) -> Option<u128> {
+        let mut order = self.nodes().into_iter().collect::<Vec<_>>();
+        order.sort_by_key(|n| self.dist[n]);
+
+        let mut counts = HashMap::<N, u128>::new();
+        for n in order {
+            let count = if n == self.start {
+                1
+            } else {
+                self.predecessors(&n)
+                    .iter()
+                    .try_fold(0u128, |acc, p| acc.checked_add(*counts.get(p).unwrap_or(&0)))?
+            };
+            counts.insert(n, count);
+        }
+
+        self.goals.iter().try_fold(0u128, |acc, g| acc.checked_add(counts[g]))
+    }
+}
+
+/// Like [`dijkstra`], but instead of stopping at the first goal keeps going until every node
+/// within the optimal cost has been settled, recording all tied predecessors along the way.
+/// Edge costs must be strictly positive.
+pub fn dijkstra_all<N, C, FN, IN, FS>(
+    start: N,
+    mut successors: FN,
+    mut success: FS,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Unsigned<C> + Ord,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut open_set = PriorityQueue::<N, Reverse<C>>::new();
+    let mut dist = HashMap::<N, C>::new();
+    let mut preds = HashMap::<N, Vec<N>>::new();
+    let mut goals = Vec::new();
+    let mut best = None;
+
+    dist.insert(start.clone(), C::ZERO);
+    open_set.push(start.clone(), Reverse(C::ZERO));
+
+    while let Some((curr, Reverse(g))) = open_set.pop() {
+        if best.is_some_and(|b| g > b) {
+            open_set.push(curr, Reverse(g));
+            break;
+        }
+        if success(&curr) {
+            best = Some(g);
+            goals.push(curr);
+            continue;
+        }
+
+        for (adj, d) in successors(&curr) {
+            let tentative_g_score = g + d;
+            match dist.entry(adj.clone()) {
+                Entry::Occupied(e) if *e.get() < tentative_g_score => continue,
+                Entry::Occupied(e) if *e.get() == tentative_g_score => {
+                    preds.entry(adj).or_default().push(curr.clone());
+                    continue;
+                }
+                Entry::Occupied(mut e) => {
+                    e.insert(tentative_g_score);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(tentative_g_score);
+                }
+            }
+            preds.insert(adj.clone(), vec![curr.clone()]);
+            open_set.push_increase(adj, Reverse(tentative_g_score));
+        }
+    }
+
+    // Whatever is still queued only has a tentative distance
+    for (n, _) in open_set {
+        dist.remove(&n);
+        preds.remove(&n);
+    }
+    best.map(|cost| ShortestPaths { start, goals, cost, dist, preds })
+}
+
+fn reconstruct_path<N>(came_from: &HashMap<N, N>, mut curr: N) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut path = vec![curr.clone()];
+    while let Some(prev) = came_from.get(&curr) {
+        path.push(prev.clone());
+        curr = prev.clone();
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topological_sort_subset_ignores_duplicates() {
+        let mut g = Graph::<u32, ()>::new();
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        assert_eq!(g.topological_sort_subset(&[3, 1, 2, 1, 3]), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn to_dot_labels_unit_valued_nodes_by_id() {
+        let mut g = Graph::<u32, ()>::new();
+        g.add_edge(1, 2);
+        let dot = g.to_dot();
+        assert!(dot.contains("[label=\"1\"]") && dot.contains("[label=\"2\"]"), "{dot}");
+    }
+
+    #[test]
+    fn to_dot_with_values_labels_nodes_by_value() {
+        let mut g = Graph::<u32, char>::new();
+        g.add_node(1, 'a');
+        g.add_edge(1, 2);
+        let dot = g.to_dot_with_values();
+        assert!(dot.contains("[label=\"a\"]") && dot.contains("[label=\"2\"]"), "{dot}");
+    }
+
+    #[test]
+    fn maximal_cliques_of_empty_graph() {
+        assert!(Graph::<u32, ()>::new().maximal_cliques().is_empty());
+    }
+
+    #[test]
+    fn count_paths_reports_overflow() {
+        // A chain of 130 diamonds has 2^130 paths from end to end
+        let mut g = Graph::<u32, ()>::new();
+        for i in 0..130 {
+            g.add_edge(3 * i, 3 * i + 1);
+            g.add_edge(3 * i, 3 * i + 2);
+            g.add_edge(3 * i + 1, 3 * i + 3);
+            g.add_edge(3 * i + 2, 3 * i + 3);
+        }
+        assert_eq!(g.count_paths(&[0], |&n| n == 3 * 70), Ok(Some(1 << 70)));
+        assert_eq!(g.count_paths(&[0], |&n| n == 3 * 130), Ok(None));
+    }
+
+    #[test]
+    fn dijkstra_all_forgets_unsettled_costs() {
+        let successors = |&n: &u32| match n {
+            0 => vec![(1, 1u32), (2, 5)],
+            _ => vec![],
+        };
+        let paths = dijkstra_all(0, successors, |&n| n == 1).unwrap();
+        assert_eq!(paths.cost_to(&1), Some(1));
+        assert_eq!(paths.cost_to(&2), None);
+    }
 }
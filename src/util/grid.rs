@@ -1,3 +1,10 @@
+use crate::util::io::{byte_lines, normalize_bytes};
+use std::{collections::VecDeque, io, ops::Index};
+
+pub const OFFSETS_4: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+pub const OFFSETS_8: [(i32, i32); 8] =
+    [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)];
+
 pub fn get_neighbors(
     pos: (usize, usize),
     w: usize,
@@ -15,14 +22,159 @@ pub fn get_neighbors(
 }
 
 pub fn get_neighbors_4(pos: (usize, usize), w: usize, h: usize) -> Vec<(usize, usize)> {
-    get_neighbors(pos, w, h, &[(-1, 0), (1, 0), (0, -1), (0, 1)])
+    get_neighbors(pos, w, h, &OFFSETS_4)
 }
 
 pub fn get_neighbors_8(pos: (usize, usize), w: usize, h: usize) -> Vec<(usize, usize)> {
-    get_neighbors(
-        pos,
-        w,
-        h,
-        &[(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)],
-    )
+    get_neighbors(pos, w, h, &OFFSETS_8)
+}
+
+/// Unit-cost distances from a set of sources, as produced by [`bfs`].
+pub struct DistanceMap {
+    w: usize,
+    h: usize,
+    dist: Vec<u32>,
+    /// Negated search offsets, leading from a cell to the cells it may have been entered from.
+    back_offsets: Vec<(i32, i32)>,
+}
+
+impl DistanceMap {
+    pub fn w(&self) -> usize { self.w }
+
+    pub fn h(&self) -> usize { self.h }
+
+    /// Distance from the nearest source, or `None` if `pos` was never reached.
+    pub fn get(&self, pos: (usize, usize)) -> Option<u32> {
+        (pos.0 < self.h && pos.1 < self.w)
+            .then(|| self.dist[pos.0 * self.w + pos.1])
+            .filter(|d| *d != u32::MAX)
+    }
+
+    pub fn is_reachable(&self, pos: (usize, usize)) -> bool { self.get(pos).is_some() }
+
+    /// Reachable positions paired with their distance, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), u32)> + '_ {
+        self.dist
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d != u32::MAX)
+            .map(|(i, d)| ((i / self.w, i % self.w), *d))
+    }
+
+    /// Walks back downhill from `pos` to one of the sources. The returned path starts at the
+    /// source and ends at `pos`, so its length is the distance plus one.
+    pub fn path_to(&self, pos: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        let mut d = self.get(pos)?;
+        let mut path = vec![pos];
+
+        let mut curr = pos;
+        while d > 0 {
+            curr = get_neighbors(curr, self.w, self.h, &self.back_offsets)
+                .into_iter()
+                .find(|n| self.get(*n) == Some(d - 1))?;
+            path.push(curr);
+            d -= 1;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search over a `w` by `h` grid using 4-neighbor moves, starting from every
+/// position in `sources` at once. Only cells for which `passable` returns true are entered from
+/// a neighbor. Sources are not checked against `passable`, but those outside the grid are skipped.
+pub fn bfs<I, F>(w: usize, h: usize, sources: I, passable: F) -> DistanceMap
+where
+    I: IntoIterator<Item = (usize, usize)>,
+    F: FnMut((usize, usize)) -> bool,
+{
+    bfs_with_offsets(w, h, sources, &OFFSETS_4, passable)
+}
+
+pub fn bfs_with_offsets<I, F>(
+    w: usize,
+    h: usize,
+    sources: I,
+    offsets: &[(i32, i32)],
+    mut passable: F,
+) -> DistanceMap
+where
+    I: IntoIterator<Item = (usize, usize)>,
+    F: FnMut((usize, usize)) -> bool,
+{
+    let mut dist = vec![u32::MAX; w * h];
+    let mut queue = VecDeque::new();
+
+    for p in sources.into_iter().filter(|p| p.0 < h && p.1 < w) {
+        if dist[p.0 * w + p.1] != 0 {
+            dist[p.0 * w + p.1] = 0;
+            queue.push_back(p);
+        }
+    }
+
+    while let Some(p) = queue.pop_front() {
+        let d = dist[p.0 * w + p.1] + 1;
+        for n in get_neighbors(p, w, h, offsets) {
+            if dist[n.0 * w + n.1] == u32::MAX && passable(n) {
+                dist[n.0 * w + n.1] = d;
+                queue.push_back(n);
+            }
+        }
+    }
+
+    let back_offsets = offsets.iter().map(|&(dy, dx)| (-dy, -dx)).collect();
+    DistanceMap { w, h, dist, back_offsets }
+}
+
+/// Rectangular grid of bytes laid over the raw input buffer, so cells are read in place instead
+/// of being copied into one allocation per row.
+pub struct ByteGrid {
+    bytes: Vec<u8>,
+    w: usize,
+    h: usize,
+    stride: usize,
+}
+
+impl ByteGrid {
+    /// Takes ownership of a file buffer and normalizes it with [`normalize_bytes`]. Fails with
+    /// [`io::ErrorKind::InvalidData`] if the rows are not all of the same width.
+    pub fn new(mut bytes: Vec<u8>) -> io::Result<Self> {
+        normalize_bytes(&mut bytes);
+
+        // Every row is now terminated by a single `\n`, the last one included
+        let w = bytes.iter().position(|&b| b == b'\n').unwrap_or(0);
+        if !byte_lines(&bytes).all(|row| row.len() == w && !row.contains(&b'\r')) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "grid rows differ in width"));
+        }
+
+        let stride = w + 1;
+        Ok(Self { h: bytes.len() / stride, bytes, w, stride })
+    }
+
+    pub fn w(&self) -> usize { self.w }
+
+    pub fn h(&self) -> usize { self.h }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<u8> {
+        (pos.0 < self.h && pos.1 < self.w).then(|| self.bytes[pos.0 * self.stride + pos.1])
+    }
+
+    pub fn row(&self, y: usize) -> &[u8] { &self.bytes[y * self.stride..][..self.w] }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> + '_ { (0..self.h).map(|y| self.row(y)) }
+
+    /// Position of the first cell holding `b`, in row-major order.
+    pub fn find(&self, b: u8) -> Option<(usize, usize)> {
+        self.rows().enumerate().find_map(|(y, row)| Some((y, row.iter().position(|&c| c == b)?)))
+    }
+}
+
+impl Index<(usize, usize)> for ByteGrid {
+    type Output = u8;
+
+    fn index(&self, pos: (usize, usize)) -> &u8 {
+        assert!(pos.1 < self.w, "column {} out of bounds for width {}", pos.1, self.w);
+        &self.bytes[pos.0 * self.stride + pos.1]
+    }
 }
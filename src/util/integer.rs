@@ -4,7 +4,6 @@ use std::ops::*;
 
 pub trait Integer<T>:
     Copy
-    + From<u8>
     + PartialEq
     + PartialOrd
     + Add<Output = T>
@@ -22,8 +21,29 @@ pub trait Integer<T>:
     const ONE: T;
     const TEN: T;
 
+    /// Converts a single decimal digit, which fits in every integer type including `i8`.
+    fn from_digit(digit: u8) -> T;
+    fn checked_add(self, rhs: T) -> Option<T>;
+    fn checked_sub(self, rhs: T) -> Option<T>;
+    fn checked_mul(self, rhs: T) -> Option<T>;
+
     fn ilog2(self) -> T;
+    fn ilog10(self) -> T;
     fn trailing_zeros(self) -> T;
+
+    /// Number of decimal digits, counting `0` as a single digit. Panics if negative.
+    fn num_digits(self) -> u32;
+    /// Decimal digits from most to least significant.
+    fn digits(self) -> impl Iterator<Item = u8>;
+    /// Appends the digits of `other` to `self`, so `12.concat(345) == 12345`.
+    fn concat(self, other: T) -> T;
+    fn checked_concat(self, other: T) -> Option<T>;
+    /// Splits off the lowest `n` digits, so `12345.split_at_digit(2) == (123, 45)`. Once `n`
+    /// exceeds the digits the type can hold everything is split off, as in `(0, 5)` for `5u64`.
+    fn split_at_digit(self, n: u32) -> (T, T);
+    /// Inverse of [`Integer::concat`]: removes `suffix` from the end of `self` if it is there and
+    /// at least one digit is left, so `12345.strip_suffix(45) == Some(123)`.
+    fn strip_suffix(self, suffix: T) -> Option<T>;
 }
 
 pub trait Unsigned<T>: Integer<T> {}
@@ -37,17 +57,78 @@ macro_rules! integer {
             const ONE: $t = 1;
             const TEN: $t = 10;
 
+            #[inline]
+            #[allow(trivial_numeric_casts)]
+            fn from_digit(digit: u8) -> $t {
+                digit as $t
+            }
+
+            #[inline]
+            fn checked_add(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_sub(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            #[inline]
+            fn checked_mul(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_mul(self, rhs)
+            }
+
             #[inline]
             #[allow(trivial_numeric_casts)]
             fn ilog2(self) -> $t {
                 <$t>::ilog2(self) as $t
             }
 
+            #[inline]
+            #[allow(trivial_numeric_casts)]
+            fn ilog10(self) -> $t {
+                <$t>::ilog10(self) as $t
+            }
+
             #[inline]
             #[allow(trivial_numeric_casts)]
             fn trailing_zeros(self) -> $t {
                 <$t>::trailing_zeros(self) as $t
             }
+
+            #[inline]
+            fn num_digits(self) -> u32 {
+                if self == 0 { 1 } else { <$t>::ilog10(self) + 1 }
+            }
+
+            #[inline]
+            #[allow(trivial_numeric_casts)]
+            fn digits(self) -> impl Iterator<Item = u8> {
+                (0..self.num_digits()).rev().map(move |i| (self / <$t>::pow(10, i) % 10) as u8)
+            }
+
+            #[inline]
+            fn concat(self, other: $t) -> $t {
+                self * <$t>::pow(10, other.num_digits()) + other
+            }
+
+            #[inline]
+            fn checked_concat(self, other: $t) -> Option<$t> {
+                <$t>::checked_pow(10, other.num_digits())
+                    .and_then(|p| self.checked_mul(p))
+                    .and_then(|n| n.checked_add(other))
+            }
+
+            #[inline]
+            fn split_at_digit(self, n: u32) -> ($t, $t) {
+                <$t>::checked_pow(10, n).map_or((0, self), |p| (self / p, self % p))
+            }
+
+            #[inline]
+            fn strip_suffix(self, suffix: $t) -> Option<$t> {
+                let p = <$t>::checked_pow(10, suffix.num_digits())?;
+                (self > suffix && self % p == suffix).then(|| self / p)
+            }
         }
     )*)
 }
@@ -58,6 +139,101 @@ macro_rules! empty_trait {
     )*)
 }
 
-integer!(u8 u16 u32 u64 u128 usize i16 i32 i64 i128);
+integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
 empty_trait!(Unsigned for u8 u16 u32 u64 u128 usize);
-empty_trait!(Signed for i16 i32 i64 i128);
+empty_trait!(Signed for i8 i16 i32 i64 i128 isize);
+
+/// Greatest common divisor of two non-negative integers.
+pub fn gcd<T: Integer<T>>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple of two non-negative integers.
+pub fn lcm<T: Integer<T>>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        T::ZERO
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the non-negative gcd of
+/// `a` and `b`.
+pub fn extended_gcd<T: Signed<T>>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// `a` reduced into `0..m`, even when negative. `m` must be positive.
+pub fn rem_euclid<T: Integer<T>>(a: T, m: T) -> T {
+    let r = a % m;
+    if r < T::ZERO {
+        r + m
+    } else {
+        r
+    }
+}
+
+/// `base` raised to `exp` modulo `m`, by repeated squaring. Intermediate products are up to
+/// `m * m`, so pick a type wide enough for that.
+pub fn mod_pow<T: Integer<T>>(base: T, mut exp: T, m: T) -> T {
+    let mut base = rem_euclid(base, m);
+    let mut result = T::ONE % m;
+
+    while exp > T::ZERO {
+        if exp & T::ONE == T::ONE {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp = exp >> T::ONE;
+    }
+
+    result
+}
+
+/// Multiplicative inverse of `a` modulo `m`, like Python's `pow(a, -1, m)`. `None` if `a` and
+/// `m` are not coprime.
+pub fn mod_inv<T: Signed<T>>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(rem_euclid(a, m), m);
+    (g == T::ONE).then(|| rem_euclid(x, m))
+}
+
+/// Chinese Remainder Theorem over `(residue, modulus)` pairs. Moduli need not be coprime.
+/// Returns the smallest non-negative solution together with the combined modulus, or `None` if
+/// the congruences are inconsistent.
+pub fn crt<T: Signed<T>>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut acc = (T::ZERO, T::ONE);
+
+    for &(r2, m2) in congruences {
+        let (r1, m1) = acc;
+        let (g, p, _) = extended_gcd(m1, m2);
+
+        let diff = r2 - r1;
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        let m = m1 / g * m2;
+        let k = rem_euclid(diff / g * p, m2 / g);
+        acc = (rem_euclid(r1 + m1 * k, m), m);
+    }
+
+    Some(acc)
+}
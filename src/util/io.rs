@@ -1,57 +1,215 @@
-use std::{
-    fs::File,
-    io,
-    io::{BufRead, Read},
-    path::Path,
-};
+use std::{fs, io, iter::FusedIterator, path::Path, str::Lines};
 
+const BOM: &[u8] = "\u{feff}".as_bytes();
+
+/// Reads a file as normalized text, see [`normalize`].
 pub fn read<P>(filename: P) -> io::Result<String>
 where
     P: AsRef<Path>,
 {
-    let mut file = File::open(filename)?;
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)?;
-    Ok(buf)
+    let text = fs::read_to_string(filename)?;
+    Ok(normalize(text))
+}
+
+/// Reads a whole file into a single normalized buffer, to be parsed in place with [`byte_lines`].
+pub fn read_bytes<P>(filename: P) -> io::Result<Vec<u8>>
+where
+    P: AsRef<Path>,
+{
+    let mut bytes = fs::read(filename)?;
+    normalize_bytes(&mut bytes);
+    Ok(bytes)
 }
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+pub fn read_lines<P>(filename: P) -> io::Result<ReadLines>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(ReadLines { text: read(filename)?, pos: 0 })
 }
 
 pub fn read_single_line<P>(filename: P) -> io::Result<String>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    let mut buf = String::new();
-    io::BufReader::new(file).read_line(&mut buf)?;
-    Ok(buf.trim_end().to_owned())
+    let mut text = read(filename)?;
+    text.truncate(text.find('\n').unwrap_or(text.len()));
+    Ok(text)
+}
+
+/// Strips a leading byte order mark, `\r\n` line endings, trailing whitespace on every line and
+/// trailing blank lines, and terminates the last line with a single `\n`.
+pub fn normalize(text: String) -> String {
+    let mut bytes = text.into_bytes();
+    normalize_bytes(&mut bytes);
+    // Only ASCII whitespace and the complete BOM are removed, so no character is cut in half
+    String::from_utf8(bytes).expect("normalization keeps text valid UTF-8")
+}
+
+/// In-place version of [`normalize`] for raw buffers.
+pub fn normalize_bytes(bytes: &mut Vec<u8>) {
+    let mut len = 0;
+    let mut read = if bytes.starts_with(BOM) { BOM.len() } else { 0 };
+
+    while read < bytes.len() {
+        let end = bytes[read..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |i| read + i);
+        let line_end = read + bytes[read..end].trim_ascii_end().len();
+
+        bytes.copy_within(read..line_end, len);
+        len += line_end - read;
+        if len < bytes.len() {
+            bytes[len] = b'\n';
+        } else {
+            bytes.push(b'\n');
+        }
+        len += 1;
+        read = end + 1;
+    }
+
+    len = bytes[..len].iter().rposition(|&b| b != b'\n').map_or(0, |i| i + 2);
+    bytes.truncate(len);
+}
+
+/// Owned lines of a normalized file, as returned by [`read_lines`].
+#[derive(Clone, Debug)]
+pub struct ReadLines {
+    text: String,
+    pos: usize,
+}
+
+impl Iterator for ReadLines {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.text.get(self.pos..).filter(|rest| !rest.is_empty())?;
+        let line = rest.split('\n').next().unwrap_or(rest);
+        self.pos += line.len() + 1;
+        Some(Ok(line.to_owned()))
+    }
+}
+
+impl FusedIterator for ReadLines {}
+
+/// Checks that `text` is a non-empty rectangular grid in which every byte of `unique` occurs
+/// exactly once, failing with [`io::ErrorKind::InvalidData`] otherwise.
+pub fn validate_grid(text: impl AsRef<[u8]>, unique: &[u8]) -> io::Result<()> {
+    let mut rows = byte_lines(text.as_ref());
+    let w = rows.next().map_or(0, <[u8]>::len);
+    if w == 0 {
+        return Err(invalid_data("grid is empty".to_owned()));
+    }
+
+    let mut counts = vec![0; unique.len()];
+    for (y, row) in byte_lines(text.as_ref()).enumerate() {
+        if row.len() != w {
+            let msg = format!("grid row {y} has width {}, expected {w}", row.len());
+            return Err(invalid_data(msg));
+        }
+        for (count, b) in counts.iter_mut().zip(unique) {
+            *count += row.iter().filter(|c| *c == b).count();
+        }
+    }
+
+    match counts.iter().zip(unique).find(|(count, _)| **count != 1) {
+        Some((count, b)) => Err(invalid_data(format!(
+            "expected exactly one '{}' in grid, found {count}",
+            b.escape_ascii()
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
 }
 
+/// Calls `f1` for every line of the first section and `f2` for every line of all later ones.
 pub fn read_lines_partitioned<P, F1, F2>(filename: P, mut f1: F1, mut f2: F2) -> io::Result<()>
 where
     P: AsRef<Path>,
     F1: FnMut(String),
     F2: FnMut(String),
 {
-    let mut seen_partition = false;
-    for line in read_lines(filename)?.flatten() {
-        if line.is_empty() {
-            seen_partition = true;
-            continue;
-        }
+    let text = read(filename)?;
+    let mut sections = sections(&text);
+    sections.next().into_iter().flatten().for_each(|line| f1(line.to_owned()));
+    sections.flatten().for_each(|line| f2(line.to_owned()));
+    Ok(())
+}
 
-        if !seen_partition {
-            f1(line);
-        } else {
-            f2(line);
+/// Iterator over the blank-line-separated sections of a text, see [`sections`].
+#[derive(Clone, Debug)]
+pub struct Sections<'a> {
+    lines: Lines<'a>,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut section = Vec::new();
+        for line in self.lines.by_ref() {
+            let line = line.trim_end();
+            if !line.is_empty() {
+                section.push(line);
+            } else if !section.is_empty() {
+                break;
+            }
         }
+        (!section.is_empty()).then_some(section)
     }
+}
 
-    Ok(())
+impl FusedIterator for Sections<'_> {}
+
+/// Splits `text` into sections separated by one or more blank lines.
+///
+/// Lines are yielded without their `\n` or `\r\n` terminator and trailing whitespace. Leading and
+/// trailing blank lines never produce empty sections.
+pub fn sections(text: &str) -> Sections<'_> { Sections { lines: text.lines() } }
+
+/// Splits `text` into exactly `N` sections, failing with [`io::ErrorKind::InvalidData`] otherwise.
+pub fn sections_exact<const N: usize>(text: &str) -> io::Result<[Vec<&str>; N]> {
+    let sections = sections(text).collect::<Vec<_>>();
+    let count = sections.len();
+    sections.try_into().map_err(|_| {
+        invalid_data(format!("expected {N} blank-line-separated sections, found {count}"))
+    })
 }
+
+/// Reads a file and splits it into its blank-line-separated sections.
+pub fn read_sections<P>(filename: P) -> io::Result<Vec<Vec<String>>>
+where
+    P: AsRef<Path>,
+{
+    let text = read(filename)?;
+    Ok(sections(&text).map(|s| s.into_iter().map(str::to_owned).collect()).collect())
+}
+
+/// Iterator over the lines of a byte buffer, see [`byte_lines`].
+#[derive(Clone, Debug)]
+pub struct ByteLines<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ByteLines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = match self.bytes.iter().position(|&b| b == b'\n') {
+            Some(i) => (&self.bytes[..i], &self.bytes[i + 1..]),
+            None => (self.bytes, &[][..]),
+        };
+        self.bytes = rest;
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+impl FusedIterator for ByteLines<'_> {}
+
+/// Splits `bytes` into lines borrowed from the buffer, without their `\n` or `\r\n` terminator.
+pub fn byte_lines(bytes: &[u8]) -> ByteLines<'_> { ByteLines { bytes } }
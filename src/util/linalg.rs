@@ -0,0 +1,164 @@
+use crate::util::integer::*;
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    num: T,
+    den: T,
+}
+
+impl<T: Signed<T>> Rational<T> {
+    pub fn new(num: T, den: T) -> Self {
+        assert!(den != T::ZERO, "Rational with zero denominator");
+
+        let g = gcd(abs(num), abs(den));
+        let (num, den) = (num / g, den / g);
+        if den < T::ZERO {
+            Self { num: -num, den: -den }
+        } else {
+            Self { num, den }
+        }
+    }
+
+    pub fn numer(&self) -> T { self.num }
+
+    pub fn denom(&self) -> T { self.den }
+
+    pub fn is_zero(&self) -> bool { self.num == T::ZERO }
+
+    pub fn is_integer(&self) -> bool { self.den == T::ONE }
+
+    pub fn to_integer(&self) -> Option<T> { self.is_integer().then_some(self.num) }
+}
+
+impl<T: Signed<T>> From<T> for Rational<T> {
+    fn from(value: T) -> Self { Self { num: value, den: T::ONE } }
+}
+
+impl<T: Signed<T>> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let den = lcm(self.den, rhs.den);
+        Self::new(self.num * (den / self.den) + rhs.num * (den / rhs.den), den)
+    }
+}
+
+impl<T: Signed<T>> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self { self + -rhs }
+}
+
+impl<T: Signed<T>> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // Cross-cancel first to keep intermediate products small
+        let g1 = gcd(abs(self.num), rhs.den);
+        let g2 = gcd(abs(rhs.num), self.den);
+        if g1 == T::ZERO || g2 == T::ZERO {
+            return Self::from(T::ZERO);
+        }
+        Self::new((self.num / g1) * (rhs.num / g2), (self.den / g2) * (rhs.den / g1))
+    }
+}
+
+impl<T: Signed<T>> Div for Rational<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(!rhs.is_zero(), "Rational division by zero");
+        self * Self::new(rhs.den, rhs.num)
+    }
+}
+
+impl<T: Signed<T>> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self { Self { num: -self.num, den: self.den } }
+}
+
+impl<T: Signed<T>> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.num * other.den).partial_cmp(&(other.num * self.den))
+    }
+}
+
+impl<T: Signed<T> + fmt::Display> fmt::Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solution<T> {
+    Unique(Vec<Rational<T>>),
+    /// Consistent, but some unknowns are free, e.g. a singular square system with a rhs in its
+    /// column space.
+    Underdetermined,
+    Inconsistent,
+}
+
+/// Solves `a * x = b` exactly by Gauss-Jordan elimination. `a` holds one row of coefficients
+/// per equation; every row must have the same length.
+pub fn solve<T: Signed<T>>(a: &[Vec<T>], b: &[T]) -> Solution<T> {
+    assert_eq!(a.len(), b.len(), "Expected one rhs value per equation");
+
+    let n = a.first().map_or(0, Vec::len);
+    let mut m: Vec<Vec<Rational<T>>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, rhs)| row.iter().chain([rhs]).map(|v| Rational::from(*v)).collect())
+        .collect();
+
+    let mut rank = 0;
+    for col in 0..n {
+        let Some(pivot) = (rank..m.len()).find(|r| !m[*r][col].is_zero()) else {
+            continue;
+        };
+        m.swap(rank, pivot);
+
+        let p = m[rank][col];
+        for v in &mut m[rank] {
+            *v = *v / p;
+        }
+
+        let pivot_row = m[rank].clone();
+        for (r, row) in m.iter_mut().enumerate() {
+            let f = row[col];
+            if r != rank && !f.is_zero() {
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *v = *v - f * *p;
+                }
+            }
+        }
+
+        rank += 1;
+    }
+
+    if m[rank..].iter().any(|row| !row[n].is_zero()) {
+        Solution::Inconsistent
+    } else if rank < n {
+        Solution::Underdetermined
+    } else {
+        Solution::Unique(m[..n].iter().map(|row| row[n]).collect())
+    }
+}
+
+fn abs<T: Signed<T>>(v: T) -> T {
+    if v < T::ZERO {
+        -v
+    } else {
+        v
+    }
+}
@@ -1,7 +1,10 @@
 // Taken from https://github.com/maneatingape/advent-of-code-rust/blob/main/src/util/parse.rs
 
 use crate::util::integer::*;
-use std::{marker::PhantomData, str::Bytes};
+use std::{
+    any::type_name, borrow::Cow, error::Error, fmt, io, iter::Copied, marker::PhantomData, slice,
+    str::FromStr,
+};
 
 pub trait ParseByte {
     fn to_decimal(self) -> u8;
@@ -12,13 +15,58 @@ impl ParseByte for u8 {
     fn to_decimal(self) -> u8 { self.wrapping_sub(b'0') }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Expected a number but found no digits.
+    MissingDigits,
+    /// A byte that is neither part of a number nor an allowed separator.
+    UnexpectedByte(u8),
+    /// The number does not fit in the requested type.
+    Overflow,
+}
+
+/// Failure from one of the fallible parsers, with the byte offset where it happened.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::MissingDigits => write!(f, "expected digits at byte {}", self.offset),
+            ParseErrorKind::UnexpectedByte(b) => {
+                write!(f, "unexpected '{}' at byte {}", b.escape_ascii(), self.offset)
+            }
+            ParseErrorKind::Overflow => write!(f, "number overflows at byte {}", self.offset),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(value: ParseError) -> Self { io::Error::new(io::ErrorKind::InvalidData, value) }
+}
+
 pub struct ParseUnsigned<'a, T> {
-    bytes: Bytes<'a>,
+    bytes: Copied<slice::Iter<'a, u8>>,
     phantom: PhantomData<T>,
 }
 
 pub struct ParseSigned<'a, T> {
-    bytes: Bytes<'a>,
+    bytes: Copied<slice::Iter<'a, u8>>,
+    phantom: PhantomData<T>,
+}
+
+pub struct ParseUnsignedStrict<'a, T> {
+    cursor: Cursor<'a>,
+    phantom: PhantomData<T>,
+}
+
+pub struct ParseSignedStrict<'a, T> {
+    cursor: Cursor<'a>,
     phantom: PhantomData<T>,
 }
 
@@ -27,36 +75,149 @@ pub trait ParseOps {
     fn signed<T: Signed<T>>(&self) -> T;
     fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T>;
     fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T>;
+
+    /// Parses the whole input as a single number, rejecting anything around it.
+    fn try_unsigned<T: Unsigned<T>>(&self) -> Result<T, ParseError>;
+    fn try_signed<T: Signed<T>>(&self) -> Result<T, ParseError>;
+
+    /// Numbers separated by runs of any of the bytes in `separators`. Unlike
+    /// [`ParseOps::iter_unsigned`], any other byte is reported as an error, after which the
+    /// iterator is exhausted.
+    fn iter_unsigned_strict<'a, T: Unsigned<T>>(
+        &'a self,
+        separators: &'a str,
+    ) -> ParseUnsignedStrict<'a, T>;
+    fn iter_signed_strict<'a, T: Signed<T>>(
+        &'a self,
+        separators: &'a str,
+    ) -> ParseSignedStrict<'a, T>;
 }
 
-impl ParseOps for &str {
+impl ParseOps for [u8] {
     fn unsigned<T: Unsigned<T>>(&self) -> T {
-        match try_unsigned(&mut self.bytes()) {
+        match next_unsigned(&mut self.iter().copied()) {
             Some(t) => t,
-            None => panic!("Unable to parse \"{self}\""),
+            None => panic!("Unable to parse \"{}\"", String::from_utf8_lossy(self)),
         }
     }
 
     fn signed<T: Signed<T>>(&self) -> T {
-        match try_signed(&mut self.bytes()) {
+        match next_signed(&mut self.iter().copied()) {
             Some(t) => t,
-            None => panic!("Unable to parse \"{self}\""),
+            None => panic!("Unable to parse \"{}\"", String::from_utf8_lossy(self)),
         }
     }
 
     fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T> {
-        ParseUnsigned { bytes: self.bytes(), phantom: PhantomData }
+        ParseUnsigned { bytes: self.iter().copied(), phantom: PhantomData }
     }
 
     fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T> {
-        ParseSigned { bytes: self.bytes(), phantom: PhantomData }
+        ParseSigned { bytes: self.iter().copied(), phantom: PhantomData }
+    }
+
+    fn try_unsigned<T: Unsigned<T>>(&self) -> Result<T, ParseError> {
+        let mut cursor = Cursor::new(self, &[]);
+        let n = cursor.unsigned()?;
+        cursor.expect_end()?;
+        Ok(n)
+    }
+
+    fn try_signed<T: Signed<T>>(&self) -> Result<T, ParseError> {
+        let mut cursor = Cursor::new(self, &[]);
+        let n = cursor.signed()?;
+        cursor.expect_end()?;
+        Ok(n)
+    }
+
+    fn iter_unsigned_strict<'a, T: Unsigned<T>>(
+        &'a self,
+        separators: &'a str,
+    ) -> ParseUnsignedStrict<'a, T> {
+        let cursor = Cursor::new(self, separators.as_bytes());
+        ParseUnsignedStrict { cursor, phantom: PhantomData }
+    }
+
+    fn iter_signed_strict<'a, T: Signed<T>>(
+        &'a self,
+        separators: &'a str,
+    ) -> ParseSignedStrict<'a, T> {
+        let cursor = Cursor::new(self, separators.as_bytes());
+        ParseSignedStrict { cursor, phantom: PhantomData }
     }
 }
 
+macro_rules! parse_ops {
+    ($($t:ty => |$s:ident| $bytes:expr),* $(,)?) => ($(
+        impl ParseOps for $t {
+            #[inline]
+            fn unsigned<T: Unsigned<T>>(&self) -> T {
+                let $s = self;
+                $bytes.unsigned()
+            }
+
+            #[inline]
+            fn signed<T: Signed<T>>(&self) -> T {
+                let $s = self;
+                $bytes.signed()
+            }
+
+            #[inline]
+            fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T> {
+                let $s = self;
+                $bytes.iter_unsigned()
+            }
+
+            #[inline]
+            fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T> {
+                let $s = self;
+                $bytes.iter_signed()
+            }
+
+            #[inline]
+            fn try_unsigned<T: Unsigned<T>>(&self) -> Result<T, ParseError> {
+                let $s = self;
+                $bytes.try_unsigned()
+            }
+
+            #[inline]
+            fn try_signed<T: Signed<T>>(&self) -> Result<T, ParseError> {
+                let $s = self;
+                $bytes.try_signed()
+            }
+
+            #[inline]
+            fn iter_unsigned_strict<'a, T: Unsigned<T>>(
+                &'a self,
+                separators: &'a str,
+            ) -> ParseUnsignedStrict<'a, T> {
+                let $s = self;
+                $bytes.iter_unsigned_strict(separators)
+            }
+
+            #[inline]
+            fn iter_signed_strict<'a, T: Signed<T>>(
+                &'a self,
+                separators: &'a str,
+            ) -> ParseSignedStrict<'a, T> {
+                let $s = self;
+                $bytes.iter_signed_strict(separators)
+            }
+        }
+    )*)
+}
+
+parse_ops!(
+    &[u8] => |s| *s,
+    &str => |s| s.as_bytes(),
+    String => |s| s.as_bytes(),
+    Cow<'_, str> => |s| s.as_bytes(),
+);
+
 impl<T: Unsigned<T>> Iterator for ParseUnsigned<'_, T> {
     type Item = T;
 
-    fn next(&mut self) -> Option<Self::Item> { try_unsigned(&mut self.bytes) }
+    fn next(&mut self) -> Option<Self::Item> { next_unsigned(&mut self.bytes) }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (lower, upper) = self.bytes.size_hint();
@@ -67,7 +228,7 @@ impl<T: Unsigned<T>> Iterator for ParseUnsigned<'_, T> {
 impl<T: Signed<T>> Iterator for ParseSigned<'_, T> {
     type Item = T;
 
-    fn next(&mut self) -> Option<Self::Item> { try_signed(&mut self.bytes) }
+    fn next(&mut self) -> Option<Self::Item> { next_signed(&mut self.bytes) }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (lower, upper) = self.bytes.size_hint();
@@ -75,13 +236,25 @@ impl<T: Signed<T>> Iterator for ParseSigned<'_, T> {
     }
 }
 
-fn try_unsigned<T: Unsigned<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
+impl<T: Unsigned<T>> Iterator for ParseUnsignedStrict<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> { self.cursor.next_item(Cursor::unsigned) }
+}
+
+impl<T: Signed<T>> Iterator for ParseSignedStrict<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> { self.cursor.next_item(Cursor::signed) }
+}
+
+fn next_unsigned<T: Unsigned<T>>(bytes: &mut impl Iterator<Item = u8>) -> Option<T> {
     let mut n = loop {
         let byte = bytes.next()?;
         let digit = byte.to_decimal();
 
         if digit < 10 {
-            break T::from(digit);
+            break T::from_digit(digit);
         }
     };
 
@@ -92,14 +265,14 @@ fn try_unsigned<T: Unsigned<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
         let digit = byte.to_decimal();
 
         if digit < 10 {
-            n = T::TEN * n + T::from(digit);
+            n = accumulate(n, digit, false).expect("Number overflows its type");
         } else {
             break Some(n);
         }
     }
 }
 
-fn try_signed<T: Signed<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
+fn next_signed<T: Signed<T>>(bytes: &mut impl Iterator<Item = u8>) -> Option<T> {
     let (mut n, negative) = loop {
         let byte = bytes.next()?;
         let digit = byte.to_decimal();
@@ -108,20 +281,256 @@ fn try_signed<T: Signed<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
             break (T::ZERO, true);
         }
         if digit < 10 {
-            break (T::from(digit), false);
+            break (T::from_digit(digit), false);
         }
     };
 
     loop {
         let Some(byte) = bytes.next() else {
-            break Some(if negative { -n } else { n });
+            break Some(n);
         };
         let digit = byte.to_decimal();
 
         if digit < 10 {
-            n = T::TEN * n + T::from(digit);
+            n = accumulate(n, digit, negative).expect("Number overflows its type");
+        } else {
+            break Some(n);
+        }
+    }
+}
+
+/// Shifts `digit` onto `n`. Negative numbers are built downwards so that `T::MIN`, which has no
+/// positive counterpart, can still be reached.
+#[inline]
+fn accumulate<T: Integer<T>>(n: T, digit: u8, negative: bool) -> Option<T> {
+    let n = n.checked_mul(T::TEN)?;
+    if negative {
+        n.checked_sub(T::from_digit(digit))
+    } else {
+        n.checked_add(T::from_digit(digit))
+    }
+}
+
+/// Position-tracking reader behind the fallible parsers.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    separators: &'a [u8],
+    pos: usize,
+    failed: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8], separators: &'a [u8]) -> Self {
+        Self { bytes, separators, pos: 0, failed: false }
+    }
+
+    fn peek(&self) -> Option<u8> { self.bytes.get(self.pos).copied() }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError { ParseError { kind, offset: self.pos } }
+
+    fn unsigned<T: Unsigned<T>>(&mut self) -> Result<T, ParseError> { self.digits(false) }
+
+    fn signed<T: Signed<T>>(&mut self) -> Result<T, ParseError> {
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+        self.digits(negative)
+    }
+
+    fn digits<T: Integer<T>>(&mut self, negative: bool) -> Result<T, ParseError> {
+        let start = self.pos;
+        let mut n = T::ZERO;
+        while let Some(digit) = self.peek().map(u8::to_decimal).filter(|d| *d < 10) {
+            n = accumulate(n, digit, negative).ok_or(self.error(ParseErrorKind::Overflow))?;
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            Err(self.error(ParseErrorKind::MissingDigits))
         } else {
-            break Some(if negative { -n } else { n });
+            Ok(n)
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(b) => Err(self.error(ParseErrorKind::UnexpectedByte(b))),
         }
     }
+
+    fn skip_separators(&mut self) {
+        while self.peek().is_some_and(|b| self.separators.contains(&b)) {
+            self.pos += 1;
+        }
+    }
+
+    fn next_item<T>(
+        &mut self,
+        parse: fn(&mut Self) -> Result<T, ParseError>,
+    ) -> Option<Result<T, ParseError>> {
+        if self.failed {
+            return None;
+        }
+
+        self.skip_separators();
+        self.peek()?;
+
+        let item = parse(self).and_then(|n| match self.peek() {
+            Some(b) if !self.separators.contains(&b) => {
+                Err(self.error(ParseErrorKind::UnexpectedByte(b)))
+            }
+            _ => Ok(n),
+        });
+        self.failed = item.is_err();
+        Some(item)
+    }
+}
+
+/// One row of raw bytes per line of `text`.
+pub fn byte_grid(text: &str) -> Vec<Vec<u8>> {
+    text.lines().map(|line| line.as_bytes().to_vec()).collect()
+}
+
+/// One row of chars per line of `text`.
+pub fn char_grid(text: &str) -> Vec<Vec<char>> {
+    text.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// One row of numbers per line of `text`, where every byte is a single decimal digit. Fails at
+/// the offset of the first other byte.
+pub fn digit_grid<T: Integer<T>>(text: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    text.lines()
+        .map(|line| {
+            let start = line.as_ptr() as usize - text.as_ptr() as usize;
+            line.bytes()
+                .enumerate()
+                .map(|(x, b)| match b.to_decimal() {
+                    digit @ 0..10 => Ok(T::from_digit(digit)),
+                    _ => {
+                        let kind = ParseErrorKind::UnexpectedByte(b);
+                        Err(ParseError { kind, offset: start + x })
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScanErrorKind {
+    /// A literal part of the pattern was not found where expected.
+    Literal(String),
+    /// A field could not be parsed as the named type.
+    Field(&'static str),
+    /// Input left over after the end of the pattern.
+    TrailingInput,
+}
+
+/// Failure from [`scan!`], carrying enough context to point at the offending input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanError {
+    pub kind: ScanErrorKind,
+    pub input: String,
+    pub pattern: &'static str,
+    pub offset: usize,
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ScanErrorKind::Literal(lit) => write!(f, "expected {lit:?} at byte {}", self.offset)?,
+            ScanErrorKind::Field(ty) => write!(f, "invalid {ty} at byte {}", self.offset)?,
+            ScanErrorKind::TrailingInput => write!(f, "trailing input at byte {}", self.offset)?,
+        }
+        write!(f, " of {:?} (pattern {:?})", self.input, self.pattern)
+    }
+}
+
+impl Error for ScanError {}
+
+impl From<ScanError> for io::Error {
+    fn from(value: ScanError) -> Self { io::Error::new(io::ErrorKind::InvalidData, value) }
+}
+
+/// Matches `input` against `pattern`, where every `{}` in the pattern stands for a field and
+/// everything else must appear literally. Each field extends up to the next occurrence of the
+/// literal following it, and is returned trimmed along with its byte offset.
+pub fn scan_fields<'a>(
+    input: &'a str,
+    pattern: &'static str,
+) -> Result<Vec<(usize, &'a str)>, ScanError> {
+    let error = |kind, offset| ScanError { kind, input: input.to_owned(), pattern, offset };
+
+    let mut literals = pattern.split("{}");
+    let first = literals.next().unwrap();
+    if !input.starts_with(first) {
+        return Err(error(ScanErrorKind::Literal(first.to_owned()), 0));
+    }
+
+    let mut pos = first.len();
+    let mut fields = Vec::new();
+    let mut literals = literals.peekable();
+    while let Some(lit) = literals.next() {
+        let end = if lit.is_empty() {
+            assert!(literals.peek().is_none(), "Adjacent fields in pattern {pattern:?}");
+            input.len()
+        } else {
+            match input[pos..].find(lit) {
+                Some(i) => pos + i,
+                None => return Err(error(ScanErrorKind::Literal(lit.to_owned()), pos)),
+            }
+        };
+
+        let field = &input[pos..end];
+        let trimmed = field.trim_start();
+        fields.push((pos + field.len() - trimmed.len(), trimmed.trim_end()));
+        pos = end + lit.len();
+    }
+
+    if pos < input.len() {
+        return Err(error(ScanErrorKind::TrailingInput, pos));
+    }
+
+    Ok(fields)
+}
+
+/// Parses one field returned by [`scan_fields`].
+pub fn scan_field<T: FromStr>(
+    input: &str,
+    pattern: &'static str,
+    (offset, field): (usize, &str),
+) -> Result<T, ScanError> {
+    field.parse().map_err(|_| ScanError {
+        kind: ScanErrorKind::Field(type_name::<T>()),
+        input: input.to_owned(),
+        pattern,
+        offset,
+    })
+}
+
+/// Extracts typed fields from a line using a `{}` template, returning a tuple with one element
+/// per listed type:
+///
+/// ```ignore
+/// let (x, y, dx, dy) = scan!(line, "p={},{} v={},{}", i32, i32, i32, i32)?;
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:literal, $($t:ty),+ $(,)?) => {{
+        let input: &str = ::std::convert::AsRef::<str>::as_ref(&$input);
+        $crate::util::parse::scan_fields(input, $pattern).and_then(|fields| {
+            assert_eq!(
+                fields.len(),
+                [$(stringify!($t)),+].len(),
+                "Field count mismatch in pattern {:?}",
+                $pattern
+            );
+            let mut fields = fields.into_iter();
+            Ok(($(
+                $crate::util::parse::scan_field::<$t>(input, $pattern, fields.next().unwrap())?,
+            )+))
+        })
+    }};
 }
@@ -1,42 +1,276 @@
-use hashbrown::HashMap;
+use std::{collections::VecDeque, convert::Infallible, fmt, iter::FusedIterator, str::FromStr};
 
-#[derive(Default)]
-struct Node {
-    pub children: HashMap<char, Node>,
-    pub is_terminal: bool,
+/// Alphabet a [`Trie`] can be keyed by.
+pub trait Symbol: Copy + Ord {
+    /// Splits a string into symbols of this alphabet.
+    fn symbols(s: &str) -> impl Iterator<Item = Self> + '_;
+
+    /// Inverse of [`Symbol::symbols`].
+    fn collect_string(symbols: &[Self]) -> String;
+}
+
+impl Symbol for u8 {
+    fn symbols(s: &str) -> impl Iterator<Item = Self> + '_ { s.bytes() }
+
+    fn collect_string(symbols: &[Self]) -> String { String::from_utf8_lossy(symbols).into_owned() }
+}
+
+impl Symbol for char {
+    fn symbols(s: &str) -> impl Iterator<Item = Self> + '_ { s.chars() }
+
+    fn collect_string(symbols: &[Self]) -> String { symbols.iter().collect() }
+}
+
+/// Children are kept sorted by symbol, which for small alphabets is both smaller and faster to
+/// search than a hash map.
+struct Node<K> {
+    children: Vec<(K, Node<K>)>,
+    is_terminal: bool,
+}
+
+impl<K> Default for Node<K> {
+    fn default() -> Self { Self { children: Vec::new(), is_terminal: false } }
+}
+
+impl<K: Symbol> Node<K> {
+    fn child(&self, k: K) -> Option<&Node<K>> {
+        self.children.binary_search_by(|(c, _)| c.cmp(&k)).ok().map(|i| &self.children[i].1)
+    }
+
+    fn child_mut(&mut self, k: K) -> Option<&mut Node<K>> {
+        self.children.binary_search_by(|(c, _)| c.cmp(&k)).ok().map(|i| &mut self.children[i].1)
+    }
+
+    fn child_or_insert(&mut self, k: K) -> &mut Node<K> {
+        let i = match self.children.binary_search_by(|(c, _)| c.cmp(&k)) {
+            Ok(i) => i,
+            Err(i) => {
+                self.children.insert(i, (k, Node::default()));
+                i
+            }
+        };
+        &mut self.children[i].1
+    }
+
+    /// Unmarks `key` below this node, dropping children left without keys. Returns whether the
+    /// key was present.
+    fn remove(&mut self, key: &[K]) -> bool {
+        let Some((&k, rest)) = key.split_first() else {
+            return std::mem::take(&mut self.is_terminal);
+        };
+        let Some(child) = self.child_mut(k) else {
+            return false;
+        };
+
+        let removed = child.remove(rest);
+        if !child.is_terminal && child.children.is_empty() {
+            self.children.retain(|(c, _)| *c != k);
+        }
+        removed
+    }
 }
 
-pub struct Trie {
-    root: Node,
-    pub max_key_len: usize,
+pub struct Trie<K = u8> {
+    root: Node<K>,
+    /// Number of stored keys of every length, without trailing zeros.
+    key_lens: Vec<usize>,
 }
 
-impl Trie {
-    pub fn new() -> Self { Self { root: Node::default(), max_key_len: 0 } }
+impl<K: Symbol> Trie<K> {
+    pub fn new() -> Self { Self { root: Node::default(), key_lens: Vec::new() } }
+
+    /// Number of stored keys.
+    pub fn len(&self) -> usize { self.key_lens.iter().sum() }
 
-    pub fn insert(&mut self, key: &str) {
+    pub fn is_empty(&self) -> bool { self.key_lens.is_empty() }
+
+    /// Length of the longest stored key, in symbols.
+    pub fn max_key_len(&self) -> usize { self.key_lens.len().saturating_sub(1) }
+
+    /// Returns whether `key` was newly inserted.
+    pub fn insert(&mut self, key: &str) -> bool { self.insert_symbols(K::symbols(key)) }
+
+    pub fn insert_symbols<I: IntoIterator<Item = K>>(&mut self, key: I) -> bool {
         let mut curr = &mut self.root;
-        for ch in key.chars() {
-            curr = curr.children.entry(ch).or_default();
+        let mut len = 0;
+        for k in key {
+            curr = curr.child_or_insert(k);
+            len += 1;
+        }
+        if std::mem::replace(&mut curr.is_terminal, true) {
+            return false;
+        }
+
+        if self.key_lens.len() <= len {
+            self.key_lens.resize(len + 1, 0);
+        }
+        self.key_lens[len] += 1;
+        true
+    }
+
+    /// Returns whether `key` was present.
+    pub fn remove(&mut self, key: &str) -> bool { self.remove_symbols(K::symbols(key)) }
+
+    pub fn remove_symbols<I: IntoIterator<Item = K>>(&mut self, key: I) -> bool {
+        let key = key.into_iter().collect::<Vec<_>>();
+        if !self.root.remove(&key) {
+            return false;
+        }
+
+        self.key_lens[key.len()] -= 1;
+        while self.key_lens.last() == Some(&0) {
+            self.key_lens.pop();
         }
-        curr.is_terminal = true;
-        self.max_key_len = self.max_key_len.max(key.len());
+        true
     }
 
-    pub fn find(&self, key: &str) -> bool {
+    pub fn find(&self, key: &str) -> bool { self.find_symbols(K::symbols(key)) }
+
+    pub fn find_symbols<I: IntoIterator<Item = K>>(&self, key: I) -> bool {
         let mut curr = &self.root;
-        for ch in key.chars() {
-            match curr.children.get(&ch) {
+        for k in key {
+            match curr.child(k) {
                 Some(next) => curr = next,
                 None => return false,
             }
         }
         curr.is_terminal
     }
+
+    /// Lengths of all keys that are a prefix of `text[offset..]`, in increasing order, found in a
+    /// single walk down the trie.
+    pub fn prefix_lengths<'a>(&'a self, text: &'a [K], offset: usize) -> PrefixLengths<'a, K> {
+        PrefixLengths { node: Some(&self.root), text: &text[offset..], len: 0 }
+    }
+
+    /// Length of the longest key that is a prefix of `text`.
+    pub fn longest_prefix_of(&self, text: &[K]) -> Option<usize> {
+        self.prefix_lengths(text, 0).last()
+    }
+
+    /// Length of the shortest key that is a prefix of `text`.
+    pub fn shortest_prefix_of(&self, text: &[K]) -> Option<usize> {
+        self.prefix_lengths(text, 0).next()
+    }
+
+    /// Number of ways to split `text` into a sequence of stored keys, or `None` if it overflows.
+    /// Empty keys are never used as pieces.
+    pub fn count_segmentations(&self, text: &[K]) -> Option<u128> {
+        let mut ways = vec![0u128; text.len() + 1];
+        ways[text.len()] = 1;
+        for i in (0..text.len()).rev() {
+            for len in self.prefix_lengths(text, i).filter(|&len| len > 0) {
+                ways[i] = ways[i].checked_add(ways[i + len])?;
+            }
+        }
+        Some(ways[0])
+    }
+
+    /// A split of `text` into as few stored keys as possible, preferring longer leading pieces
+    /// among equally short splits.
+    pub fn min_segmentation<'a>(&self, text: &'a [K]) -> Option<Vec<&'a [K]>> {
+        // Fewest pieces needed for every suffix, with the length of the first one
+        let mut best: Vec<Option<(usize, usize)>> = vec![None; text.len() + 1];
+        best[text.len()] = Some((0, 0));
+        for i in (0..text.len()).rev() {
+            best[i] = self
+                .prefix_lengths(text, i)
+                .filter(|&len| len > 0)
+                .filter_map(|len| best[i + len].map(|(pieces, _)| (pieces + 1, len)))
+                .min_by_key(|&(pieces, len)| (pieces, std::cmp::Reverse(len)));
+        }
+
+        best[0]?;
+        let mut pieces = Vec::new();
+        let mut i = 0;
+        while i < text.len() {
+            let (_, len) = best[i].unwrap();
+            pieces.push(&text[i..i + len]);
+            i += len;
+        }
+        Some(pieces)
+    }
+
+    /// Every split of `text` into stored keys. Their number can grow exponentially with the
+    /// length of `text`, see [`Trie::count_segmentations`].
+    pub fn segmentations<'a>(&self, text: &'a [K]) -> Vec<Vec<&'a [K]>> {
+        let mut completable = vec![false; text.len() + 1];
+        completable[text.len()] = true;
+        for i in (0..text.len()).rev() {
+            completable[i] =
+                self.prefix_lengths(text, i).any(|len| len > 0 && completable[i + len]);
+        }
+
+        let mut all = Vec::new();
+        if completable[0] {
+            self.extend_segmentations(text, 0, &completable, &mut Vec::new(), &mut all);
+        }
+        all
+    }
+
+    fn extend_segmentations<'a>(
+        &self,
+        text: &'a [K],
+        i: usize,
+        completable: &[bool],
+        pieces: &mut Vec<&'a [K]>,
+        all: &mut Vec<Vec<&'a [K]>>,
+    ) {
+        if i == text.len() {
+            all.push(pieces.clone());
+            return;
+        }
+        for len in self.prefix_lengths(text, i).filter(|&len| len > 0 && completable[i + len]) {
+            pieces.push(&text[i..i + len]);
+            self.extend_segmentations(text, i + len, completable, pieces, all);
+            pieces.pop();
+        }
+    }
+
+    /// Stored keys in lexicographic order.
+    pub fn keys(&self) -> Keys<'_, K> {
+        Keys { stack: vec![(&self.root, None, 0)], path: Vec::new() }
+    }
+
+    /// Numbers the nodes breadth-first with the root as 0, returning the `(parent, symbol)` edge
+    /// leading into every other node. Siblings appear in symbol order.
+    pub(crate) fn edges_bfs(&self) -> Vec<(usize, K)> {
+        let mut edges = Vec::new();
+        let mut queue = VecDeque::from([(0, &self.root)]);
+        while let Some((id, node)) = queue.pop_front() {
+            for (k, child) in &node.children {
+                edges.push((id, *k));
+                queue.push_back((edges.len(), child));
+            }
+        }
+        edges
+    }
+}
+
+impl<K: Symbol> Default for Trie<K> {
+    fn default() -> Self { Self::new() }
 }
 
-impl<'a, I> From<I> for Trie
+/// One key per line, in lexicographic order.
+impl<K: Symbol> fmt::Display for Trie<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in self.keys() {
+            writeln!(f, "{}", K::collect_string(&key))?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads back the output of [`Display`](fmt::Display), one key per line.
+impl<K: Symbol> FromStr for Trie<K> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(Self::from(s.lines())) }
+}
+
+impl<'a, K, I> From<I> for Trie<K>
 where
+    K: Symbol,
     I: Iterator<Item = &'a str>,
 {
     fn from(value: I) -> Self {
@@ -47,3 +281,52 @@ where
         trie
     }
 }
+
+/// Iterator returned by [`Trie::prefix_lengths`].
+pub struct PrefixLengths<'a, K> {
+    node: Option<&'a Node<K>>,
+    text: &'a [K],
+    len: usize,
+}
+
+impl<K: Symbol> Iterator for PrefixLengths<'_, K> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(node) = self.node {
+            let len = self.len;
+            self.node = self.text.get(len).and_then(|&k| node.child(k));
+            self.len += 1;
+            if node.is_terminal {
+                return Some(len);
+            }
+        }
+        None
+    }
+}
+
+impl<K: Symbol> FusedIterator for PrefixLengths<'_, K> {}
+
+/// Iterator returned by [`Trie::keys`].
+pub struct Keys<'a, K> {
+    stack: Vec<(&'a Node<K>, Option<K>, usize)>,
+    path: Vec<K>,
+}
+
+impl<K: Symbol> Iterator for Keys<'_, K> {
+    type Item = Vec<K>;
+
+    fn next(&mut self) -> Option<Vec<K>> {
+        while let Some((node, k, depth)) = self.stack.pop() {
+            self.path.truncate(depth.saturating_sub(1));
+            self.path.extend(k);
+            self.stack.extend(node.children.iter().rev().map(|(c, n)| (n, Some(*c), depth + 1)));
+            if node.is_terminal {
+                return Some(self.path.clone());
+            }
+        }
+        None
+    }
+}
+
+impl<K: Symbol> FusedIterator for Keys<'_, K> {}
@@ -1,19 +1,17 @@
-use crate::util::io::read_lines;
-use itertools::Itertools;
-use regex::Regex;
+use crate::{
+    scan,
+    util::{counter::Counter, io::read_lines},
+};
 use std::io;
 
 type Input = (Vec<i32>, Vec<i32>);
 
 pub fn parse(filepath: &str) -> io::Result<Input> {
-    let re = Regex::new(r"(\d+)\s+(\d+)").unwrap();
-
     let (mut l1, mut l2): (Vec<_>, Vec<_>) = read_lines(filepath)?
         .flatten()
-        .map(|line| {
-            let (_, [n, m]) = re.captures(line.as_str()).unwrap().extract();
-            (n.parse::<i32>().unwrap(), m.parse::<i32>().unwrap())
-        })
+        .map(|line| scan!(line, "{} {}", i32, i32))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
         .unzip();
 
     l1.sort();
@@ -27,6 +25,6 @@ pub fn part1(input: &Input) -> Option<u32> {
 }
 
 pub fn part2(input: &Input) -> Option<i32> {
-    let l2_counts = input.1.iter().counts();
-    input.0.iter().map(|n| n * *l2_counts.get(n).unwrap_or(&0) as i32).sum::<i32>().into()
+    let l2_counts: Counter<_> = input.1.iter().collect();
+    input.0.iter().map(|n| n * l2_counts.get(&n) as i32).sum::<i32>().into()
 }
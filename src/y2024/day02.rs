@@ -7,8 +7,8 @@ type Input = (Vec<Vec<i32>>, Vec<Vec<i32>>);
 pub fn parse(filepath: &str) -> io::Result<Input> {
     let reports = read_lines(filepath)?
         .flatten()
-        .map(|line| line.as_str().iter_signed().collect::<Vec<i32>>())
-        .collect::<Vec<Vec<i32>>>();
+        .map(|line| line.iter_signed_strict(" ").collect::<Result<Vec<i32>, _>>())
+        .collect::<Result<Vec<Vec<i32>>, _>>()?;
 
     Ok(reports.into_iter().partition(|report| is_safe(report)))
 }
@@ -1,6 +1,4 @@
-use crate::util::io::read_lines;
-use itertools::Itertools;
-use regex::Regex;
+use crate::util::{aho_corasick::AhoCorasick, io::read_bytes};
 use std::io;
 
 pub enum Instruction {
@@ -12,25 +10,19 @@ pub enum Instruction {
 type Input = Vec<Instruction>;
 
 pub fn parse(filepath: &str) -> io::Result<Vec<Instruction>> {
-    let re = Regex::new(r"(do)\(\)|(don't)\(\)|(mul)\((\d{1,3}),(\d{1,3})\)").unwrap();
+    let memory = read_bytes(filepath)?;
+    let ac = AhoCorasick::new(["do()", "don't()", "mul("].map(str::as_bytes));
 
-    let mut instructions = Vec::<Instruction>::new();
-
-    for line in read_lines(filepath)?.flatten() {
-        for c in re.captures_iter(&line) {
-            // This regex returns 5 groups, and each instruction is non-overlapping, so
-            // we can filter out the None groups, and be left with a tag group, followed by
-            // the arguments for that tag if they exist
-            let cap_str = c.iter().filter_map(|m| m).map(|m| m.as_str()).skip(1).collect_vec();
-
-            instructions.push(match cap_str[0] {
-                "do" => Instruction::Do,
-                "don't" => Instruction::DoNot,
-                "mul" => Instruction::Mul(cap_str[1].parse().unwrap(), cap_str[2].parse().unwrap()),
-                _ => unreachable!(),
-            });
-        }
-    }
+    // The tags can't overlap each other, so every match starts a separate instruction. Only mul
+    // takes arguments, which still have to be checked after the tag
+    let instructions = ac
+        .find_iter(&memory)
+        .filter_map(|m| match m.pattern {
+            0 => Some(Instruction::Do),
+            1 => Some(Instruction::DoNot),
+            _ => mul_args(&memory[m.end..]).map(|(n, m)| Instruction::Mul(n, m)),
+        })
+        .collect();
 
     Ok(instructions)
 }
@@ -62,3 +54,16 @@ pub fn part2(input: &Input) -> Option<i32> {
         .0
         .into()
 }
+
+/// Parses `X,Y)` with one to three digits per number.
+fn mul_args(bytes: &[u8]) -> Option<(i32, i32)> {
+    fn number(bytes: &[u8]) -> Option<(i32, &[u8])> {
+        let len = bytes.iter().take(3).take_while(|b| b.is_ascii_digit()).count();
+        let n = bytes[..len].iter().fold(0, |n, b| n * 10 + (b - b'0') as i32);
+        (len > 0).then_some((n, &bytes[len..]))
+    }
+
+    let (n, rest) = number(bytes)?;
+    let (m, rest) = number(rest.strip_prefix(b",")?)?;
+    rest.starts_with(b")").then_some((n, m))
+}
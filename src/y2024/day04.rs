@@ -1,30 +1,27 @@
-use crate::util::io::read_lines;
+use crate::util::{
+    aho_corasick::AhoCorasick,
+    grid::ByteGrid,
+    io::{read_bytes, validate_grid},
+};
 use std::io;
 
-type Input = Vec<Vec<char>>;
+type Input = ByteGrid;
 
-pub fn parse(filepath: &str) -> io::Result<Input> {
-    Ok(read_lines(filepath)?.flatten().map(|line| line.chars().collect()).collect())
-}
+pub fn validate(text: &str) -> io::Result<()> { validate_grid(text, b"") }
 
-pub fn part1(input: &Input) -> Option<u32> {
-    let mut ans = 0;
-    for y in 0..input.len() {
-        for x in 0..input[y].len() {
-            if input[y][x] != 'X' {
-                continue;
-            }
-            ans += count_xmas(input, y, x);
-        }
-    }
-    Some(ans)
+pub fn parse(filepath: &str) -> io::Result<Input> { ByteGrid::new(read_bytes(filepath)?) }
+
+pub fn part1(input: &Input) -> Option<usize> {
+    // Searching for the word and its reverse along every line covers all eight directions
+    let ac = AhoCorasick::new([b"XMAS", b"SAMX"]);
+    lines(input).map(|line| ac.count(&line)).sum::<usize>().into()
 }
 
 pub fn part2(input: &Input) -> Option<u32> {
     let mut ans = 0;
-    for y in 1..input.len() - 1 {
-        for x in 1..input[y].len() - 1 {
-            if input[y][x] != 'A' {
+    for y in 1..input.h() - 1 {
+        for x in 1..input.w() - 1 {
+            if input[(y, x)] != b'A' {
                 continue;
             }
 
@@ -36,45 +33,35 @@ pub fn part2(input: &Input) -> Option<u32> {
     Some(ans)
 }
 
-fn count_xmas(word_search: &[Vec<char>], y: usize, x: usize) -> u32 {
-    let mut n = 0;
+/// Every row, column, diagonal and anti-diagonal of the grid.
+fn lines(grid: &ByteGrid) -> impl Iterator<Item = Vec<u8>> + '_ {
+    let (w, h) = (grid.w(), grid.h());
+    let diagonal = move |start: (usize, usize), dx: isize| {
+        (0..)
+            .map(move |i| (start.0 + i, start.1.wrapping_add_signed(i as isize * dx)))
+            .map_while(|pos| grid.get(pos))
+            .collect()
+    };
 
-    let word: Vec<char> = vec!['X', 'M', 'A', 'S'];
-    for dy in -1..=1 {
-        for dx in -1..=1 {
-            if dy == 0 && dx == 0 {
-                continue;
-            }
-
-            let mut found_word = true;
-            for i in 0..word.len() {
-                let pos =
-                    ((y as i32 + (i as i32 * dy)) as usize, (x as i32 + (i as i32 * dx)) as usize);
-
-                let c = word_search.get(pos.0).and_then(|row| row.get(pos.1));
-                if *c.unwrap_or(&'\0') != word[i] {
-                    found_word = false;
-                    break;
-                }
-            }
-            if found_word {
-                n += 1;
-            }
-        }
-    }
+    let rows = (0..h).map(|y| grid.row(y).to_vec());
+    let cols = (0..w).map(move |x| (0..h).map(|y| grid[(y, x)]).collect());
+    let starts = (0..w).map(|x| (0, x)).chain((1..h).map(move |y| (y, 0)));
+    let diags = starts.map(move |start| diagonal(start, 1));
+    let anti_starts = (0..w).map(|x| (0, x)).chain((1..h).map(move |y| (y, w - 1)));
+    let anti_diags = anti_starts.map(move |start| diagonal(start, -1));
 
-    n
+    rows.chain(cols).chain(diags).chain(anti_diags)
 }
 
-fn check_x_mas(word_search: &[Vec<char>], y: usize, x: usize) -> bool {
-    let tl = word_search[y - 1][x - 1];
-    let bl = word_search[y + 1][x - 1];
-    let tr = word_search[y - 1][x + 1];
-    let br = word_search[y + 1][x + 1];
+fn check_x_mas(word_search: &ByteGrid, y: usize, x: usize) -> bool {
+    let tl = word_search[(y - 1, x - 1)];
+    let bl = word_search[(y + 1, x - 1)];
+    let tr = word_search[(y - 1, x + 1)];
+    let br = word_search[(y + 1, x + 1)];
 
     // Only four possibilities, A is always anchored in the middle
-    (tl == 'M' && bl == 'M' && tr == 'S' && br == 'S')
-        || (tl == 'S' && bl == 'S' && tr == 'M' && br == 'M')
-        || (tl == 'M' && bl == 'S' && tr == 'M' && br == 'S')
-        || (tl == 'S' && bl == 'M' && tr == 'S' && br == 'M')
+    (tl == b'M' && bl == b'M' && tr == b'S' && br == b'S')
+        || (tl == b'S' && bl == b'S' && tr == b'M' && br == b'M')
+        || (tl == b'M' && bl == b'S' && tr == b'M' && br == b'S')
+        || (tl == b'S' && bl == b'M' && tr == b'S' && br == b'M')
 }
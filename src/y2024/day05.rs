@@ -1,37 +1,29 @@
-use crate::util::{io::read_lines_partitioned, parse::ParseOps};
+use crate::util::{graph::Graph, io::read_lines_partitioned, parse::ParseOps};
 use itertools::Itertools;
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-    io,
-};
+use std::io;
 
-type Input = (HashMap<u32, HashSet<u32>>, (Vec<Vec<u32>>, Vec<Vec<u32>>));
+type Rules = Graph<u32, ()>;
+type Input = (Rules, (Vec<Vec<u32>>, Vec<Vec<u32>>));
 
 pub fn parse(filepath: &str) -> io::Result<Input> {
-    let mut ordering = HashMap::<u32, HashSet<u32>>::new();
+    let mut rules = Rules::new();
     let mut updates: Vec<Vec<u32>> = Vec::new();
 
     read_lines_partitioned(
         filepath,
         |line| {
-            let (before, after) = line.as_str().iter_unsigned().collect_tuple().unwrap();
-            if let Some(hs) = ordering.get_mut(&before) {
-                hs.insert(after);
-            } else {
-                ordering.insert(before, HashSet::from([after]));
-            }
+            let (before, after) = line.iter_unsigned().collect_tuple().unwrap();
+            rules.add_edge(before, after);
         },
         |line| {
-            updates.push(line.as_str().iter_unsigned().collect());
+            updates.push(line.iter_unsigned().collect());
         },
     )?;
 
-    let updates: (Vec<_>, Vec<_>) = updates.into_iter().partition(|u| {
-        u.is_sorted_by(|a, b| ordering.get(b).and_then(|o| Some(!o.contains(a))).unwrap_or(true))
-    });
+    let updates: (Vec<_>, Vec<_>) =
+        updates.into_iter().partition(|u| u.is_sorted_by(|a, b| !rules.has_edge(b, a)));
 
-    Ok((ordering, updates))
+    Ok((rules, updates))
 }
 
 pub fn part1(input: &Input) -> Option<u32> {
@@ -39,24 +31,12 @@ pub fn part1(input: &Input) -> Option<u32> {
 }
 
 pub fn part2(input: &Input) -> Option<u32> {
+    // The full rule set is cyclic, only the rules between pages of a single update form a DAG
     input
         .1
          .1
         .iter()
-        .map(|u| {
-            let mut u = u.to_owned();
-            u.sort_by(|a, b| {
-                input
-                    .0
-                    .get(b)
-                    .and_then(|o| {
-                        Some(if o.contains(a) { Ordering::Greater } else { Ordering::Less })
-                    })
-                    .unwrap_or(Ordering::Equal)
-            });
-            u
-        })
-        .map(|u| u[u.len() / 2])
-        .sum::<u32>()
-        .into()
+        .map(|u| input.0.topological_sort_subset(u).map(|u| u[u.len() / 2]))
+        .sum::<Result<u32, _>>()
+        .ok()
 }
@@ -1,4 +1,4 @@
-use crate::util::{io::read_lines, parse::ParseOps};
+use crate::util::{integer::Integer, io::read_lines, parse::ParseOps};
 use itertools::{Either, Itertools};
 use std::io;
 
@@ -8,7 +8,7 @@ pub fn parse(filepath: &str) -> io::Result<Input> {
     let equations = read_lines(filepath)?
         .flatten()
         .map(|line| {
-            let ns = line.as_str().iter_unsigned().collect_vec();
+            let ns = line.iter_unsigned().collect_vec();
             (ns[0], ns[1..].to_vec())
         })
         .collect::<Vec<(u64, Vec<u64>)>>();
@@ -47,9 +47,7 @@ fn is_solvable(ops: &[Op], value: u64, numbers: &[u64]) -> Option<u64> {
                 .flat_map(|op| match op {
                     Op::Add => acc.checked_add(numbers[0]),
                     Op::Mul => acc.checked_mul(numbers[0]),
-                    Op::Cat => acc
-                        .checked_mul(10u64.pow(numbers[0].ilog10() + 1))
-                        .and_then(|x| x.checked_add(numbers[0])),
+                    Op::Cat => acc.checked_concat(numbers[0]),
                 })
                 .any(|x| match &numbers[1..] {
                     [] => x == value,
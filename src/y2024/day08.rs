@@ -1,19 +1,21 @@
-use crate::util::io::read_lines;
+use crate::util::io::{byte_lines, read_bytes, validate_grid};
 use itertools::Itertools;
 use std::{collections::HashMap, io, iter::once};
 
-type Input = (i32, i32, HashMap<char, Vec<(i32, i32)>>);
+type Input = (i32, i32, HashMap<u8, Vec<(i32, i32)>>);
+
+pub fn validate(text: &str) -> io::Result<()> { validate_grid(text, b"") }
 
 pub fn parse(filepath: &str) -> io::Result<Input> {
     let mut map_w: i32 = 0;
     let mut map_h: i32 = 0;
-    let mut locs: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
+    let mut locs: HashMap<u8, Vec<(i32, i32)>> = HashMap::new();
 
-    for (y, line) in read_lines(filepath)?.flatten().enumerate() {
+    for (y, line) in byte_lines(&read_bytes(filepath)?).enumerate() {
         map_h = map_h.max(y as i32 + 1);
         map_w = map_w.max(line.len() as i32);
 
-        for (x, c) in line.chars().enumerate().filter(|(_, c)| *c != '.') {
+        for (x, &c) in line.iter().enumerate().filter(|(_, c)| **c != b'.') {
             let pos = (x as i32, y as i32);
             if let Some(v) = locs.get_mut(&c) {
                 v.push(pos);
@@ -34,7 +36,7 @@ pub fn part2(input: &Input) -> Option<usize> {
     calculate_ans(input.0, input.1, &input.2, 0i32..).into()
 }
 
-fn calculate_ans<I>(map_w: i32, map_h: i32, locs: &HashMap<char, Vec<(i32, i32)>>, it: I) -> usize
+fn calculate_ans<I>(map_w: i32, map_h: i32, locs: &HashMap<u8, Vec<(i32, i32)>>, it: I) -> usize
 where
     I: Iterator<Item = i32> + Clone,
 {
@@ -1,15 +1,18 @@
-use crate::util::{graph::Graph, grid::get_neighbors_4, io::read_lines};
-use hashlink::LinkedHashSet;
-use std::{collections::HashSet, io};
+use crate::util::{
+    graph::Graph,
+    grid::get_neighbors_4,
+    io::{read, validate_grid},
+    parse::digit_grid,
+};
+use std::io;
 
 type G = Graph<(usize, usize), u32>;
 type Input = (G, Vec<(usize, usize)>);
 
+pub fn validate(text: &str) -> io::Result<()> { validate_grid(text, b"") }
+
 pub fn parse(filename: &str) -> io::Result<Input> {
-    let map: Vec<Vec<u32>> = read_lines(filename)?
-        .flatten()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect();
+    let map: Vec<Vec<u32>> = digit_grid(&read(filename)?)?;
 
     let mut trailheads: Vec<(usize, usize)> = Vec::new();
     let mut graph = G::new();
@@ -33,58 +36,12 @@ pub fn parse(filename: &str) -> io::Result<Input> {
     Ok((graph, trailheads))
 }
 
-pub fn part1(input: &Input) -> Option<u32> {
-    input.1.iter().map(|start| score_trailhead(&input.0, start)).sum::<u32>().into()
-}
-
-pub fn part2(input: &Input) -> Option<u32> {
-    input.1.iter().map(|start| rate_path(&input.0, start)).sum::<u32>().into()
+pub fn part1(input: &Input) -> Option<usize> {
+    input.0.count_reachable(&input.1, |p| is_trail_end(&input.0, p)).ok()
 }
 
-fn score_trailhead(graph: &G, start: &(usize, usize)) -> u32 {
-    let mut score = 0;
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
-
-    let mut stack: Vec<(usize, usize)> = Vec::from([*start]);
-    while !stack.is_empty() {
-        let v = stack.pop().unwrap();
-        if visited.insert(v) {
-            if graph.val(&v).unwrap() == &9 {
-                score += 1;
-            } else {
-                if let Some(neighbors) = graph.adj(&v) {
-                    for n in neighbors {
-                        stack.push(*n);
-                    }
-                }
-            }
-        }
-    }
-
-    score
+pub fn part2(input: &Input) -> Option<u128> {
+    input.0.count_paths(&input.1, |p| is_trail_end(&input.0, p)).ok().flatten()
 }
 
-fn rate_path(graph: &G, start: &(usize, usize)) -> u32 {
-    fn inner(graph: &G, visited: &mut LinkedHashSet<(usize, usize)>, rating: &mut u32) {
-        if let Some(adj) = graph.adj(visited.back().unwrap()) {
-            for n in adj {
-                if visited.contains(n) {
-                    continue;
-                }
-                if graph.val(n).unwrap() == &9 {
-                    *rating += 1;
-                }
-                visited.insert(*n);
-                inner(graph, visited, rating);
-                visited.pop_back();
-            }
-        }
-    }
-
-    let mut visited = LinkedHashSet::new();
-    visited.insert(*start);
-    let mut rating = 0;
-    inner(graph, &mut visited, &mut rating);
-
-    rating
-}
+fn is_trail_end(graph: &G, pos: &(usize, usize)) -> bool { graph.val(pos) == Some(&9) }
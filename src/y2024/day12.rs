@@ -1,13 +1,16 @@
-use crate::util::io::read_lines;
+use crate::util::{
+    bitset::BitGrid,
+    io::{read, validate_grid},
+};
 use std::{io, iter::once};
 
 type Input = Vec<Vec<char>>;
 
+pub fn validate(text: &str) -> io::Result<()> { validate_grid(text, b"") }
+
 pub fn parse(filename: &str) -> io::Result<Input> {
-    let map: Input = read_lines(filename)?
-        .flatten()
-        .map(|line| format!("\0{line}\0").chars().collect())
-        .collect();
+    let text = read(filename)?;
+    let map: Input = text.lines().map(|line| format!("\0{line}\0").chars().collect()).collect();
 
     let w = map[0].len();
     Ok(once(vec!['\0'; w]).chain(map.into_iter()).chain(once(vec!['\0'; w])).collect())
@@ -15,11 +18,11 @@ pub fn parse(filename: &str) -> io::Result<Input> {
 
 pub fn part1(input: &Input) -> Option<i32> {
     let mut price = 0;
-    let mut visited = vec![vec![false; input[0].len()]; input.len()];
+    let mut visited = BitGrid::new(input[0].len(), input.len());
 
     for y in 1..input.len() - 1 {
         for x in 1..input[y].len() - 1 {
-            if !visited[y][x] {
+            if !visited.contains((y, x)) {
                 price += flood_region(input, &mut visited, y, x);
             }
         }
@@ -30,11 +33,11 @@ pub fn part1(input: &Input) -> Option<i32> {
 
 pub fn part2(input: &Input) -> Option<i32> {
     let mut price = 0;
-    let mut visited = vec![vec![false; input[0].len()]; input.len()];
+    let mut visited = BitGrid::new(input[0].len(), input.len());
 
     for y in 1..input.len() - 1 {
         for x in 1..input[y].len() - 1 {
-            if !visited[y][x] {
+            if !visited.contains((y, x)) {
                 price += flood_region_bulk_discount(input, &mut visited, y, x);
             }
         }
@@ -43,12 +46,7 @@ pub fn part2(input: &Input) -> Option<i32> {
     Some(price)
 }
 
-fn flood_region(
-    map: &[Vec<char>],
-    visited: &mut [Vec<bool>],
-    start_y: usize,
-    start_x: usize,
-) -> i32 {
+fn flood_region(map: &[Vec<char>], visited: &mut BitGrid, start_y: usize, start_x: usize) -> i32 {
     let mut area = 0;
     let mut perimeter = 0;
 
@@ -57,7 +55,7 @@ fn flood_region(
     let mut stack = Vec::from([(start_y, start_x)]);
     while !stack.is_empty() {
         let p = stack.pop().unwrap();
-        if !visited[p.0][p.1] {
+        if visited.insert(p) {
             area += 1;
 
             for o in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
@@ -69,7 +67,6 @@ fn flood_region(
                 }
             }
         }
-        visited[p.0][p.1] = true;
     }
 
     area * perimeter
@@ -77,7 +74,7 @@ fn flood_region(
 
 fn flood_region_bulk_discount(
     map: &[Vec<char>],
-    visited: &mut [Vec<bool>],
+    visited: &mut BitGrid,
     start_y: usize,
     start_x: usize,
 ) -> i32 {
@@ -89,7 +86,7 @@ fn flood_region_bulk_discount(
     let mut stack = Vec::from([(start_y, start_x)]);
     while !stack.is_empty() {
         let p = stack.pop().unwrap();
-        if !visited[p.0][p.1] {
+        if visited.insert(p) {
             area += 1;
 
             let mut kernel = [['\0'; 3]; 3];
@@ -123,7 +120,6 @@ fn flood_region_bulk_discount(
                 }
             }
         }
-        visited[p.0][p.1] = true;
     }
 
     area * corners
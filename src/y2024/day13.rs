@@ -1,11 +1,15 @@
-use crate::util::{io::read, parse::ParseOps};
+use crate::util::{
+    integer::extended_gcd,
+    io::read,
+    linalg::{solve, Solution},
+    parse::ParseOps,
+};
 use std::io;
 
 type Input = (Vec<Option<i64>>, Vec<Option<i64>>);
 
 pub fn parse(filepath: &str) -> io::Result<Input> {
     let (p1, p2): (Vec<_>, Vec<_>) = read(filepath)?
-        .as_str()
         .iter_signed::<i64>()
         .array_chunks::<6>()
         .map(|xs| (solve_system(&xs, 0), solve_system(&xs, 1e13 as i64)))
@@ -17,9 +21,52 @@ pub fn part1(input: &Input) -> Option<i64> { input.0.iter().flatten().sum::<i64>
 
 pub fn part2(input: &Input) -> Option<i64> { input.1.iter().flatten().sum::<i64>().into() }
 
-fn solve_system(xs: &[i64], c: i64) -> Option<i64> {
-    let d = xs[0] * xs[3] - xs[2] * xs[1];
-    let x = ((xs[4] + c) * xs[3] - xs[2] * (xs[5] + c)) / d;
-    let y = (xs[0] * (xs[5] + c) - (xs[4] + c) * xs[1]) / d;
-    (xs[0] * x + xs[2] * y == xs[4] + c && xs[1] * x + xs[3] * y == xs[5] + c).then_some(x * 3 + y)
+fn solve_system(xs: &[i64; 6], c: i64) -> Option<i64> {
+    let [ax, ay, bx, by, px, py] = xs.map(i128::from);
+    let c = i128::from(c);
+
+    match solve(&[vec![ax, bx], vec![ay, by]], &[px + c, py + c]) {
+        Solution::Unique(v) => {
+            let (a, b) = (v[0].to_integer()?, v[1].to_integer()?);
+            (a >= 0 && b >= 0).then(|| (a * 3 + b) as i64)
+        }
+        // Both buttons move the claw along the same line, so one nonzero row says it all
+        Solution::Underdetermined => {
+            let (da, db, p) = if (ax, bx) != (0, 0) { (ax, bx, px + c) } else { (ay, by, py + c) };
+            cheapest_on_line(da, db, p).map(|cost| cost as i64)
+        }
+        Solution::Inconsistent => None,
+    }
+}
+
+/// Cheapest `3 * a + b` over non-negative `a` and `b` with `a * da + b * db == p`.
+fn cheapest_on_line(da: i128, db: i128, p: i128) -> Option<i128> {
+    if da == 0 || db == 0 {
+        let (step, cost) = if da == 0 { (db, 1) } else { (da, 3) };
+        return match step {
+            0 => (p == 0).then_some(0),
+            _ => (p % step == 0 && p / step >= 0).then(|| p / step * cost),
+        };
+    }
+
+    let (g, x, y) = extended_gcd(da, db);
+    if p % g != 0 {
+        return None;
+    }
+
+    // Every solution is `(a0 + k * sa, b0 + k * sb)`, and the cost is linear in `k`, so the
+    // cheapest one sits at an end of the range of `k` keeping both presses non-negative
+    let (a0, b0, sa, sb) = (x * (p / g), y * (p / g), db / g, -da / g);
+    let (mut lo, mut hi) = (None, None);
+    for (c, s) in [(a0, sa), (b0, sb)] {
+        if s > 0 {
+            lo = lo.max(Some(-c.div_euclid(s)));
+        } else {
+            hi = Some(hi.map_or(c.div_euclid(-s), |h: i128| h.min(c.div_euclid(-s))));
+        }
+    }
+    if lo.zip(hi).is_some_and(|(lo, hi)| lo > hi) {
+        return None;
+    }
+    [lo, hi].into_iter().flatten().map(|k| 3 * (a0 + k * sa) + b0 + k * sb).min()
 }
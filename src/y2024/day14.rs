@@ -1,19 +1,20 @@
-use crate::util::io::read_lines;
+use crate::{
+    scan,
+    util::{integer::crt, io::read_lines},
+};
 use itertools::Itertools;
-use regex::Regex;
 use std::io;
 
 type Input = (Vec<((i32, i32), (i32, i32))>, i32, i32);
 
 pub fn parse(filepath: &str) -> io::Result<Input> {
-    let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
     let robots = read_lines(filepath)?
         .flatten()
         .map(|line| {
-            let (_, [x, y, dx, dy]) = re.captures(&line).map(|c| c.extract()).unwrap();
-            ((x.parse().unwrap(), y.parse().unwrap()), (dx.parse().unwrap(), dy.parse().unwrap()))
+            let (x, y, dx, dy) = scan!(line, "p={},{} v={},{}", i32, i32, i32, i32)?;
+            Ok(((x, y), (dx, dy)))
         })
-        .collect();
+        .collect::<io::Result<_>>()?;
     Ok((robots, 101, 103))
 }
 
@@ -88,10 +89,8 @@ pub fn part2(input: &Input) -> Option<i32> {
         }
     }
 
-    // TODO: Make a 3 argument pow function to replicate Python's
-    //       We're taking advantage of the fact that we know inverse(w) = 51
-    //       In Python: pow(w, -1, h)
-    Some(bx + (((51 * (by - bx)) % h) * w))
+    // t = bx (mod w) and t = by (mod h)
+    crt(&[(bx, w), (by, h)]).map(|(t, _)| t)
 }
 
 fn variance(xs: &[f32]) -> f32 {
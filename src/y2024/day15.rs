@@ -1,6 +1,9 @@
-use crate::util::io::read_lines_partitioned;
+use crate::util::{
+    bitset::BitGrid,
+    io::{read_lines_partitioned, sections_exact, validate_grid},
+};
 use itertools::Itertools;
-use std::{cmp::PartialEq, collections::HashSet, io, io::Write};
+use std::{cmp::PartialEq, io, io::Write};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -26,6 +29,12 @@ type Pos = (usize, usize);
 
 type Input = (Warehouse, Pos, Vec<Move>);
 
+/// The warehouse must be rectangular with a single robot, and be followed by the moves.
+pub fn validate(text: &str) -> io::Result<()> {
+    let [warehouse, _] = sections_exact(text)?;
+    validate_grid(warehouse.join("\n"), b"@")
+}
+
 pub fn parse(filepath: &str) -> io::Result<Input> {
     let mut warehouse = Warehouse::new();
     let mut moves = Vec::new();
@@ -191,7 +200,7 @@ fn search_first_none(warehouse: &Warehouse, start: Pos, dir: &Dir) -> Option<Pos
 fn wide_up_down(warehouse: &mut Warehouse, start: Pos, dir: &Dir) -> Option<Vec<Pos>> {
     let mut to_move = Vec::<Pos>::new();
 
-    let mut checked = HashSet::<Pos>::new();
+    let mut checked = BitGrid::new(warehouse[0].len(), warehouse.len());
     let mut stack = Vec::<Pos>::from([start]);
     while !stack.is_empty() {
         let p = stack.pop().unwrap();
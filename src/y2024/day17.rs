@@ -1,6 +1,8 @@
-use crate::util::io::read_lines;
+use crate::{
+    scan,
+    util::io::{invalid_data, read, sections_exact},
+};
 use itertools::Itertools;
-use regex::Regex;
 use std::io;
 
 #[derive(Debug)]
@@ -31,23 +33,36 @@ pub fn parse(filepath: &str) -> io::Result<Input> {
     let mut prog = Program::new();
     let mut quine = Vec::new();
 
-    let re = Regex::new(r"Register ([ABC]): (\d+)|(Program): ((?:\d+,\d+,?)+)$").unwrap();
-    let _ = read_lines(filepath)?.flatten().filter(|line| !line.is_empty()).for_each(|line| {
-        let (_, [k, v]) = re.captures(&line).map(|c| c.extract()).unwrap();
+    let text = read(filepath)?;
+    let [registers, program] = sections_exact(&text)?;
+
+    for line in registers {
+        let (k, v) = scan!(line, "Register {}: {}", char, u64)?;
         match k {
-            "A" => regs.a = v.parse().unwrap(),
-            "B" => regs.b = v.parse().unwrap(),
-            "C" => regs.c = v.parse().unwrap(),
-            "Program" => {
-                quine = v.split(',').map(|n| n.parse::<u64>().unwrap()).collect();
-                for chunk in quine.chunks_exact(2) {
-                    let [opcode, operand] = chunk else { unreachable!() };
-                    prog.push(((*opcode).into(), *operand));
-                }
+            'A' => regs.a = v,
+            'B' => regs.b = v,
+            'C' => regs.c = v,
+            _ => return Err(invalid_data(format!("unknown register {k:?}"))),
+        }
+    }
+
+    for line in program {
+        let (v,) = scan!(line, "Program: {}", String)?;
+        quine = v
+            .split(',')
+            .map(|n| n.parse::<u64>().map_err(|e| invalid_data(format!("{e}: {n:?}"))))
+            .collect::<io::Result<Vec<_>>>()?;
+        if quine.len() % 2 != 0 {
+            return Err(invalid_data(format!("program has odd length {}", quine.len())));
+        }
+        for chunk in quine.chunks_exact(2) {
+            let [opcode, operand] = chunk else { unreachable!() };
+            if *opcode > 7 {
+                return Err(invalid_data(format!("unknown opcode {opcode}")));
             }
-            _ => unreachable!(),
+            prog.push(((*opcode).into(), *operand));
         }
-    });
+    }
 
     Ok((regs, prog, quine))
 }
@@ -1,33 +1,31 @@
-use crate::util::{io::read_lines, trie::*};
-use hashlink::LruCache;
+use crate::util::{
+    io::{read, sections_exact},
+    trie::*,
+};
 use std::io;
 
-type Input = Vec<u64>;
+type Input = Vec<u128>;
 
 pub fn parse(filepath: &str) -> io::Result<Input> {
-    let mut lines = read_lines(filepath)?.flatten().filter(|l| !l.is_empty());
+    let text = read(filepath)?;
+    let [patterns, designs] = sections_exact(&text)?;
 
-    let trie = Trie::from(lines.next().unwrap().split(", "));
+    let trie: Trie = Trie::from(patterns.iter().flat_map(|l| l.split(", ")));
 
-    let mut memo: LruCache<String, u64> = LruCache::new(25);
-    Ok(lines.map(|l| count_possible(&trie, &l, &mut memo)).filter(|&n| n > 0).collect())
+    let mut arrangements = Vec::new();
+    for design in designs {
+        let n = trie.count_segmentations(design.as_bytes()).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("too many arrangements: {design}"))
+        })?;
+        if n > 0 {
+            arrangements.push(n);
+        }
+    }
+    Ok(arrangements)
 }
 
 pub fn part1(input: &Input) -> Option<usize> { input.len().into() }
 
-pub fn part2(input: &Input) -> Option<u64> { input.iter().sum::<u64>().into() }
-
-fn count_possible(trie: &Trie, d: &str, memo: &mut LruCache<String, u64>) -> u64 {
-    *match memo.get(d) {
-        Some(c) => c,
-        None => {
-            let mut total = if trie.find(d) { 1 } else { 0 };
-            for i in (1..d.len().min(trie.max_key_len + 1)).rev() {
-                if trie.find(&d[..i]) {
-                    total += count_possible(trie, &d[i..], memo);
-                }
-            }
-            memo.entry(d.to_owned()).or_insert(total)
-        }
-    }
+pub fn part2(input: &Input) -> Option<u128> {
+    input.iter().try_fold(0u128, |acc, n| acc.checked_add(*n))
 }
@@ -1,34 +1,33 @@
 //! Race Condition
 
-use crate::util::io::read_lines;
+use crate::util::{
+    grid::bfs,
+    io::{read, validate_grid},
+};
 use std::io;
 
 type Pos = (usize, usize);
 type Racetrack = Vec<Vec<u32>>;
 type Input = (Racetrack, u32);
 
+pub fn validate(text: &str) -> io::Result<()> { validate_grid(text, b"SE") }
+
 pub fn parse(filepath: &str) -> io::Result<Input> {
     let mut track_len = 0;
-    let mut start = (0, 0);
     let mut end = (0, 0);
 
-    let mut racetrack = read_lines(filepath)?
-        .flatten()
+    let mut racetrack = read(filepath)?
+        .lines()
         .enumerate()
         .map(|(y, l)| {
             l.char_indices()
                 .map(|(x, c)| match c {
-                    'S' => {
-                        track_len += 1;
-                        start = (y, x);
-                        0
-                    }
                     'E' => {
                         track_len += 1;
                         end = (y, x);
                         0
                     }
-                    '.' => {
+                    'S' | '.' => {
                         track_len += 1;
                         0
                     }
@@ -38,29 +37,16 @@ pub fn parse(filepath: &str) -> io::Result<Input> {
                 .collect()
         })
         .collect();
-    mark_dist_from_end(&mut racetrack, track_len, &start, &end);
+    mark_dist_from_end(&mut racetrack, &end);
 
     Ok((racetrack, track_len))
 }
 
-fn mark_dist_from_end(racetrack: &mut Racetrack, track_len: u32, start: &Pos, end: &Pos) {
-    let mut curr = *start;
-    let mut dist_from_end = track_len - 1;
-    loop {
-        racetrack[curr.0][curr.1] = dist_from_end;
-        dist_from_end -= 1;
-
-        for o in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
-            let next = ((curr.0 as i32 + o.0) as usize, (curr.1 as i32 + o.1) as usize);
-            if racetrack[next.0][next.1] == 0 {
-                curr = next;
-                break;
-            }
-        }
-
-        if curr == *end {
-            break;
-        }
+fn mark_dist_from_end(racetrack: &mut Racetrack, end: &Pos) {
+    let (w, h) = (racetrack[0].len(), racetrack.len());
+    let dists = bfs(w, h, [*end], |(y, x)| racetrack[y][x] != u32::MAX);
+    for ((y, x), d) in dists.iter() {
+        racetrack[y][x] = d;
     }
 }
 